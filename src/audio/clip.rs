@@ -1,11 +1,37 @@
-use std::io::Cursor;
+use std::io::{Cursor, Read};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
 
 use crate::error::AppError;
+use crate::realtime::audio::HighPass;
 use rodio::{Decoder, Source};
+use serde::{Deserialize, Serialize};
+
+/// Window [`AudioClip::level_at`] takes the peak over.
+const LEVEL_WINDOW: Duration = Duration::from_millis(120);
+/// Analysis frame for [`AudioClip::looks_like_speech`].
+const SPEECH_FRAME: Duration = Duration::from_millis(20);
+/// Frames with an RMS level above this count as active (about -34 dBFS).
+pub(super) const ACTIVE_FRAME_RMS: f32 = 0.02;
+/// Below this share of active frames the clip is treated as silence.
+const MIN_ACTIVE_RATIO: f32 = 0.05;
+/// Speech pauses between phrases; sound that never drops out is more likely
+/// music or steady noise.
+const MAX_ACTIVE_RATIO: f32 = 0.97;
+const CONTINUOUS_SOUND_MIN: Duration = Duration::from_secs(5);
+/// Voiced speech crosses zero far less often than hiss or white noise.
+const MAX_SPEECH_ZERO_CROSSING_RATE: f32 = 0.3;
+/// RMS level [`AudioClip::normalize`] aims for, about -20 dBFS.
+const NORMALIZE_TARGET_RMS: f32 = 0.1;
+/// Normalization never lifts a peak above this, just under full scale.
+const NORMALIZE_PEAK_CEILING: f32 = 0.98;
+/// Clips quieter than this are silence and are left alone.
+const NORMALIZE_MIN_RMS: f32 = 1e-4;
+/// Full scale of a 24-bit PCM sample.
+const PCM24_MAX: f32 = 8_388_607.0;
 
 #[derive(Clone)]
 pub struct AudioClip {
@@ -13,6 +39,43 @@ pub struct AudioClip {
     pub channels: u16,
     samples: Vec<f32>,
     wav_bytes: Option<Arc<Vec<u8>>>,
+    source_depth: Option<WavDepth>,
+}
+
+/// Sample format of an exported WAV file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WavDepth {
+    #[default]
+    Pcm16,
+    Pcm24,
+    Float32,
+}
+
+impl WavDepth {
+    pub const ALL: [WavDepth; 3] = [WavDepth::Pcm16, WavDepth::Pcm24, WavDepth::Float32];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WavDepth::Pcm16 => "16-bit",
+            WavDepth::Pcm24 => "24-bit",
+            WavDepth::Float32 => "32-bit float",
+        }
+    }
+
+    fn spec(self, sample_rate: u32, channels: u16) -> hound::WavSpec {
+        let (bits_per_sample, sample_format) = match self {
+            WavDepth::Pcm16 => (16, hound::SampleFormat::Int),
+            WavDepth::Pcm24 => (24, hound::SampleFormat::Int),
+            WavDepth::Float32 => (32, hound::SampleFormat::Float),
+        };
+        hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample,
+            sample_format,
+        }
+    }
 }
 
 impl AudioClip {
@@ -22,9 +85,18 @@ impl AudioClip {
             channels,
             samples,
             wav_bytes: None,
+            source_depth: None,
         }
     }
 
+    pub fn from_pcm16(samples: &[i16], sample_rate: u32, channels: u16) -> Self {
+        let samples = samples
+            .iter()
+            .map(|sample| *sample as f32 / i16::MAX as f32)
+            .collect();
+        Self::from_samples(samples, sample_rate, channels)
+    }
+
     pub fn from_wav_bytes(bytes: Vec<u8>) -> Result<Self, AppError> {
         match Self::decode_wav_bytes(&bytes) {
             Ok(mut clip) => {
@@ -35,14 +107,33 @@ impl AudioClip {
         }
     }
 
+    /// Reads a WAV file straight from disk without keeping a copy of its
+    /// bytes alongside the decoded samples.
+    pub fn from_wav_file(path: &Path) -> Result<Self, AppError> {
+        let reader = hound::WavReader::open(path)
+            .with_context(|| format!("Failed to open WAV file {}", path.display()))
+            .map_err(AppError::from)?;
+        Self::decode_wav(reader)
+    }
+
     fn decode_wav_bytes(bytes: &[u8]) -> Result<Self, AppError> {
         let cursor = Cursor::new(bytes.to_vec());
-        let mut reader = hound::WavReader::new(cursor)
+        let reader = hound::WavReader::new(cursor)
             .context("Failed to parse WAV data")
             .map_err(AppError::from)?;
+        Self::decode_wav(reader)
+    }
+
+    fn decode_wav<R: Read>(mut reader: hound::WavReader<R>) -> Result<Self, AppError> {
         let spec = reader.spec();
         let channels = spec.channels;
         let sample_rate = spec.sample_rate;
+        let source_depth = match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Int, 24) => WavDepth::Pcm24,
+            (hound::SampleFormat::Int, bits) if bits > 24 => WavDepth::Float32,
+            (hound::SampleFormat::Float, _) => WavDepth::Float32,
+            _ => WavDepth::Pcm16,
+        };
         let samples: Vec<f32> = match spec.sample_format {
             hound::SampleFormat::Float => reader
                 .samples::<f32>()
@@ -57,7 +148,11 @@ impl AudioClip {
                     .samples::<i16>()
                     .map(|res| res.unwrap_or(0) as f32 / i16::MAX as f32)
                     .collect(),
-                24 | 32 => reader
+                24 => reader
+                    .samples::<i32>()
+                    .map(|res| res.unwrap_or(0) as f32 / PCM24_MAX)
+                    .collect(),
+                32 => reader
                     .samples::<i32>()
                     .map(|res| res.unwrap_or(0) as f32 / i32::MAX as f32)
                     .collect(),
@@ -73,6 +168,7 @@ impl AudioClip {
             channels,
             samples,
             wav_bytes: None,
+            source_depth: Some(source_depth),
         })
     }
 
@@ -85,9 +181,10 @@ impl AudioClip {
         let samples: Vec<f32> = decoder.convert_samples::<f32>().collect();
         Ok(Self {
             sample_rate,
-            channels: channels as u16,
+            channels,
             samples,
             wav_bytes: Some(Arc::new(bytes)),
+            source_depth: None,
         })
     }
 
@@ -100,10 +197,90 @@ impl AudioClip {
         Duration::from_secs_f64(seconds)
     }
 
+    /// Interleaved samples in the range -1.0 to 1.0.
     pub fn samples(&self) -> &[f32] {
         &self.samples
     }
 
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Largest absolute sample across all channels; 0.0 for an empty clip.
+    pub fn peak(&self) -> f32 {
+        self.samples
+            .iter()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+    }
+
+    /// Root mean square over all samples; 0.0 for an empty clip.
+    pub fn rms(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        (self.samples.iter().map(|s| s * s).sum::<f32>() / self.samples.len() as f32).sqrt()
+    }
+
+    pub fn append_samples(&mut self, samples: &[f32]) {
+        self.samples.extend_from_slice(samples);
+        self.wav_bytes = None;
+    }
+
+    /// Returns a copy with content below `cutoff_hz` attenuated, e.g. to
+    /// remove rumble before transcription. The original is left untouched
+    /// for playback.
+    pub fn high_pass(&self, cutoff_hz: f32) -> AudioClip {
+        let channels = self.channels.max(1) as usize;
+        let mut filters = vec![HighPass::new(cutoff_hz, self.sample_rate); channels];
+        let mut samples = self.samples.clone();
+        for frame in samples.chunks_mut(channels) {
+            for (sample, filter) in frame.iter_mut().zip(&mut filters) {
+                filter.process(std::slice::from_mut(sample));
+            }
+        }
+        AudioClip::from_samples(samples, self.sample_rate, self.channels)
+    }
+
+    /// Returns a copy at a consistent loudness, so different TTS voices can be
+    /// compared fairly. The gain is capped so the loudest sample stays below
+    /// full scale; already-loud clips are turned down rather than clipped.
+    pub fn normalize(&self) -> AudioClip {
+        self.with_gain(self.normalization_gain())
+    }
+
+    /// Gain [`Self::normalize`] applies.
+    pub fn normalization_gain(&self) -> f32 {
+        let rms = self.rms();
+        if rms < NORMALIZE_MIN_RMS {
+            return 1.0;
+        }
+        self.limit_gain(NORMALIZE_TARGET_RMS / rms)
+    }
+
+    /// Lowers `gain` where needed so this clip's peak stays below full
+    /// scale. Used to apply one gain across the chunks of streamed speech.
+    pub fn limit_gain(&self, gain: f32) -> f32 {
+        let peak = self.peak();
+        if peak > 0.0 {
+            gain.min(NORMALIZE_PEAK_CEILING / peak)
+        } else {
+            gain
+        }
+    }
+
+    pub fn with_gain(&self, gain: f32) -> AudioClip {
+        let samples = self
+            .samples
+            .iter()
+            .map(|sample| (sample * gain).clamp(-1.0, 1.0))
+            .collect();
+        AudioClip::from_samples(samples, self.sample_rate, self.channels)
+    }
+
     pub fn wav_bytes(&mut self) -> Result<Arc<Vec<u8>>, AppError> {
         if let Some(bytes) = &self.wav_bytes {
             return Ok(bytes.clone());
@@ -114,59 +291,161 @@ impl AudioClip {
         Ok(arc)
     }
 
+    /// Sample format of the WAV file the clip was loaded from; `None` for
+    /// recorded or synthesized audio.
+    pub fn source_depth(&self) -> Option<WavDepth> {
+        self.source_depth
+    }
+
+    /// Encodes the clip for saving: at the depth of the file it was loaded
+    /// from, or at `preferred` for recorded audio.
+    pub fn export_wav(&self, preferred: WavDepth) -> Result<Vec<u8>, AppError> {
+        self.encode_wav(self.source_depth.unwrap_or(preferred))
+    }
+
+    /// Peak level, across all channels, of the window centred on
+    /// `timestamp`. 0.0 for an empty clip and for timestamps at or past the
+    /// end, which the player's clock reports when the output lags.
     pub fn level_at(&self, timestamp: Duration) -> f32 {
-        if self.samples.is_empty() {
+        let channels = self.channels as usize;
+        if channels == 0 {
             return 0.0;
         }
-        let window = Duration::from_millis(120);
-        let frames_per_window = ((self.sample_rate as f64) * window.as_secs_f64()) as usize;
-        let frames_per_window = frames_per_window.max(1);
-        let center_frame = (timestamp.as_secs_f64() * self.sample_rate as f64) as usize;
-
-        let channels = self.channels as usize;
         let total_frames = self.samples.len() / channels;
-        if total_frames == 0 {
+        let center_frame = (timestamp.as_secs_f64() * self.sample_rate as f64) as usize;
+        if center_frame >= total_frames {
             return 0.0;
         }
-        let start_frame = center_frame
-            .saturating_sub(frames_per_window / 2)
-            .min(total_frames);
+        let frames_per_window =
+            ((self.sample_rate as f64 * LEVEL_WINDOW.as_secs_f64()) as usize).max(1);
+        let start_frame = center_frame.saturating_sub(frames_per_window / 2);
         let end_frame = (start_frame + frames_per_window).min(total_frames);
-        if start_frame >= end_frame {
-            return 0.0;
-        }
+        self.samples[start_frame * channels..end_frame * channels]
+            .iter()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+            .min(1.0)
+    }
 
-        let mut max_amp = 0.0f32;
-        for frame in start_frame..end_frame {
-            let idx = frame * channels;
-            for ch in 0..channels {
-                let sample = self.samples[idx + ch].abs();
-                if sample > max_amp {
-                    max_amp = sample;
-                }
+    /// Downsampled waveform for thumbnails: the clip split into `count`
+    /// equal stretches, each reduced to its largest absolute sample across
+    /// all channels. Shorter clips give one value per frame.
+    pub fn peaks(&self, count: usize) -> Vec<f32> {
+        let channels = self.channels.max(1) as usize;
+        let frames = self.samples.len() / channels;
+        let count = count.min(frames);
+        (0..count)
+            .map(|bin| {
+                let start = bin * frames / count * channels;
+                let end = (bin + 1) * frames / count * channels;
+                self.samples[start..end]
+                    .iter()
+                    .fold(0.0f32, |peak, sample| peak.max(sample.abs()))
+                    .min(1.0)
+            })
+            .collect()
+    }
+
+    /// Rough guess whether the clip contains speech, from the share of frames
+    /// with sound in them and how often those frames cross zero. It flags
+    /// silence, hiss and long stretches of uninterrupted sound such as music;
+    /// callers should treat a `false` as a reason to ask, not to refuse.
+    pub fn looks_like_speech(&self) -> bool {
+        let channels = self.channels.max(1) as usize;
+        let frame_len =
+            ((self.sample_rate as f64 * SPEECH_FRAME.as_secs_f64()) as usize).max(1) * channels;
+        let mut frames = 0usize;
+        let mut active = 0usize;
+        let mut crossing_rate_sum = 0.0f32;
+        for frame in self.samples.chunks(frame_len) {
+            frames += 1;
+            let mono: Vec<f32> = frame
+                .chunks(channels)
+                .map(|samples| samples.iter().sum::<f32>() / samples.len() as f32)
+                .collect();
+            let rms = (mono.iter().map(|s| s * s).sum::<f32>() / mono.len() as f32).sqrt();
+            if rms < ACTIVE_FRAME_RMS {
+                continue;
             }
+            active += 1;
+            let crossings = mono
+                .windows(2)
+                .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+                .count();
+            crossing_rate_sum += crossings as f32 / mono.len() as f32;
+        }
+        if frames == 0 || active == 0 {
+            return false;
+        }
+        let active_ratio = active as f32 / frames as f32;
+        if active_ratio < MIN_ACTIVE_RATIO {
+            return false;
+        }
+        if active_ratio > MAX_ACTIVE_RATIO && self.duration() >= CONTINUOUS_SOUND_MIN {
+            return false;
         }
-        max_amp.min(1.0)
+        crossing_rate_sum / (active as f32) <= MAX_SPEECH_ZERO_CROSSING_RATE
     }
 
+    /// 16-bit PCM for playback.
     fn render_wav(&self) -> Result<Vec<u8>, AppError> {
-        let spec = hound::WavSpec {
-            channels: self.channels,
-            sample_rate: self.sample_rate,
-            bits_per_sample: 16,
-            sample_format: hound::SampleFormat::Int,
-        };
+        self.encode_wav(WavDepth::Pcm16)
+    }
+
+    /// Encodes the clip as FLAC at the sample rate and channel count it has,
+    /// with 24-bit samples for clips loaded from 24-bit files and 16-bit
+    /// otherwise. Decoding gives back exactly the PCM a WAV export holds.
+    #[cfg(feature = "flac")]
+    pub fn to_flac(&self) -> Result<Vec<u8>, AppError> {
+        if !(1..=8).contains(&self.channels) {
+            return Err(AppError::Audio(format!(
+                "FLAC supports 1 to 8 channels, not {}",
+                self.channels
+            )));
+        }
+        if self.sample_rate == 0 || self.sample_rate >= 1 << 20 {
+            return Err(AppError::Audio(format!(
+                "Unsupported FLAC sample rate: {} Hz",
+                self.sample_rate
+            )));
+        }
+        let wide = self.source_depth == Some(WavDepth::Pcm24);
+        let samples: Vec<i32> = self
+            .samples
+            .iter()
+            .map(|sample| {
+                let sample = sample.clamp(-1.0, 1.0);
+                if wide {
+                    (sample * PCM24_MAX).round() as i32
+                } else {
+                    (sample * i16::MAX as f32) as i16 as i32
+                }
+            })
+            .collect();
+        let bits = if wide { 24 } else { 16 };
+        Ok(super::flac::encode(
+            &samples,
+            self.sample_rate,
+            self.channels,
+            bits,
+        ))
+    }
+
+    fn encode_wav(&self, depth: WavDepth) -> Result<Vec<u8>, AppError> {
+        let spec = depth.spec(self.sample_rate, self.channels);
         let mut cursor = Cursor::new(Vec::new());
         {
             let mut writer = hound::WavWriter::new(&mut cursor, spec)
                 .context("Failed to create WAV writer")
                 .map_err(AppError::from)?;
             for sample in &self.samples {
-                let scaled = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
-                writer
-                    .write_sample(scaled)
-                    .context("Failed writing WAV sample")
-                    .map_err(AppError::from)?;
+                let sample = sample.clamp(-1.0, 1.0);
+                match depth {
+                    WavDepth::Pcm16 => writer.write_sample((sample * i16::MAX as f32) as i16),
+                    WavDepth::Pcm24 => writer.write_sample((sample * PCM24_MAX).round() as i32),
+                    WavDepth::Float32 => writer.write_sample(sample),
+                }
+                .context("Failed writing WAV sample")
+                .map_err(AppError::from)?;
             }
             writer
                 .finalize()
@@ -176,3 +455,223 @@ impl AudioClip {
         Ok(cursor.into_inner())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    fn encode(samples: &[i32], bits_per_sample: u16) -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: RATE,
+            bits_per_sample,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for sample in samples {
+            writer.write_sample(*sample).unwrap();
+        }
+        writer.finalize().unwrap();
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn exports_loaded_clips_at_their_original_depth() {
+        let original = [0, 1, -1, 8_388_607, -8_388_607, 1_234_567];
+        let clip = AudioClip::from_wav_bytes(encode(&original, 24)).unwrap();
+        assert_eq!(clip.source_depth(), Some(WavDepth::Pcm24));
+        assert!((clip.samples()[3] - 1.0).abs() < 1e-6);
+
+        let exported = clip.export_wav(WavDepth::Pcm16).unwrap();
+        let mut reader = hound::WavReader::new(Cursor::new(exported)).unwrap();
+        assert_eq!(reader.spec().bits_per_sample, 24);
+        let round_trip: Vec<i32> = reader.samples::<i32>().map(Result::unwrap).collect();
+        assert_eq!(round_trip, original);
+
+        let recorded = AudioClip::from_samples(vec![0.5, -0.25], RATE, 1);
+        for (preferred, bits) in [(WavDepth::Pcm16, 16), (WavDepth::Float32, 32)] {
+            let exported = recorded.export_wav(preferred).unwrap();
+            let reader = hound::WavReader::new(Cursor::new(exported)).unwrap();
+            assert_eq!(reader.spec().bits_per_sample, bits);
+        }
+        let float = AudioClip::from_wav_bytes(recorded.export_wav(WavDepth::Float32).unwrap());
+        assert_eq!(float.unwrap().samples(), recorded.samples());
+    }
+
+    fn tone(seconds: f32, frequency: f32, amplitude: f32) -> Vec<f32> {
+        (0..(seconds * RATE as f32) as usize)
+            .map(|i| amplitude * (i as f32 * frequency * std::f32::consts::TAU / RATE as f32).sin())
+            .collect()
+    }
+
+    /// Energy of `samples` at `frequency`, from a single DFT bin.
+    fn energy_at(samples: &[f32], frequency: f32) -> f32 {
+        let (re, im) = samples
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, s)| {
+                let phase = i as f32 * frequency * std::f32::consts::TAU / RATE as f32;
+                (re + s * phase.cos(), im - s * phase.sin())
+            });
+        (re * re + im * im) / samples.len() as f32
+    }
+
+    #[test]
+    fn high_pass_removes_rumble_and_keeps_voice_band() {
+        let samples: Vec<f32> = tone(2.0, 30.0, 0.4)
+            .iter()
+            .zip(tone(2.0, 1_000.0, 0.2))
+            .map(|(low, high)| low + high)
+            .collect();
+        let clip = AudioClip::from_samples(samples, RATE, 1);
+        let filtered = clip.high_pass(80.0);
+        // Skip the filter's settling time.
+        let settled = |clip: &AudioClip| clip.samples()[RATE as usize / 2..].to_vec();
+        let (before, after) = (settled(&clip), settled(&filtered));
+
+        assert!(energy_at(&after, 30.0) < energy_at(&before, 30.0) * 0.05);
+        let kept = energy_at(&after, 1_000.0) / energy_at(&before, 1_000.0);
+        assert!((0.9..1.1).contains(&kept), "{kept}");
+        assert_eq!(clip.samples().len(), filtered.samples().len());
+    }
+
+    #[test]
+    fn normalizes_towards_target_without_clipping() {
+        let quiet = AudioClip::from_samples(tone(0.5, 220.0, 0.02), RATE, 1).normalize();
+        assert!((quiet.rms() - NORMALIZE_TARGET_RMS).abs() < 0.005);
+
+        // A full-scale square wave already has its RMS at the peak; it must
+        // come down to the ceiling instead of being pushed into clipping.
+        let square: Vec<f32> = (0..RATE)
+            .map(|i| if (i / 40) % 2 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let loud = AudioClip::from_samples(square, RATE, 1).normalize();
+        assert!(loud.peak() <= NORMALIZE_PEAK_CEILING + 1e-6);
+
+        let silence = AudioClip::from_samples(vec![0.0; 100], RATE, 1);
+        assert_eq!(silence.normalization_gain(), 1.0);
+    }
+
+    #[test]
+    fn reports_metadata_duration_and_levels() {
+        let samples: Vec<f32> = (0..RATE).flat_map(|_| [0.5, -0.5]).collect();
+        let clip = AudioClip::from_samples(samples, RATE, 2);
+        assert_eq!((clip.sample_rate(), clip.channels()), (RATE, 2));
+        assert_eq!(clip.duration(), Duration::from_secs(1));
+        assert_eq!(clip.peak(), 0.5);
+        assert!((clip.rms() - 0.5).abs() < 1e-6);
+        assert_eq!(clip.level_at(Duration::ZERO), 0.5);
+        assert_eq!(clip.level_at(Duration::from_millis(500)), 0.5);
+        assert_eq!(clip.level_at(Duration::from_secs(5)), 0.0);
+
+        let empty = AudioClip::from_samples(Vec::new(), RATE, 1);
+        assert_eq!(empty.duration(), Duration::ZERO);
+        assert_eq!((empty.peak(), empty.rms()), (0.0, 0.0));
+        assert_eq!(empty.level_at(Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn level_is_zero_from_the_end_of_the_clip() {
+        // 100 ms at full level followed by one odd sample of a partial frame.
+        let mut samples = vec![1.0; 2 * RATE as usize / 10];
+        samples.push(1.0);
+        let clip = AudioClip::from_samples(samples, RATE, 2);
+        let end = clip.duration();
+        assert_eq!(clip.level_at(end - Duration::from_millis(1)), 1.0);
+        assert_eq!(clip.level_at(end), 0.0);
+        assert_eq!(clip.level_at(end + Duration::from_millis(10)), 0.0);
+        assert_eq!(clip.level_at(Duration::MAX), 0.0);
+
+        let no_channels = AudioClip::from_samples(vec![0.5; 16], RATE, 0);
+        assert_eq!(no_channels.level_at(Duration::ZERO), 0.0);
+    }
+
+    #[test]
+    fn rendered_wav_decodes_to_the_same_clip() {
+        let samples = vec![0.0, 0.5, -0.5, 0.25, -1.0, 1.0];
+        let clip = AudioClip::from_samples(samples.clone(), 22_050, 2);
+        let decoded = AudioClip::decode_wav_bytes(&clip.render_wav().unwrap()).unwrap();
+        assert_eq!((decoded.sample_rate(), decoded.channels()), (22_050, 2));
+        assert_eq!(decoded.duration(), clip.duration());
+        assert_eq!(decoded.samples().len(), samples.len());
+        for (decoded, original) in decoded.samples().iter().zip(&samples) {
+            assert!((decoded - original).abs() < 1e-4, "{decoded} vs {original}");
+        }
+    }
+
+    #[cfg(feature = "flac")]
+    #[test]
+    fn flac_export_matches_wav_export() {
+        let clip = AudioClip::from_samples(tone(0.6, 330.0, 0.4).repeat(2), RATE, 2);
+        let flac = clip.to_flac().unwrap();
+        let wav = clip.export_wav(WavDepth::Pcm16).unwrap();
+        assert!(flac.len() < wav.len());
+
+        let mut reader = claxon::FlacReader::new(Cursor::new(flac)).unwrap();
+        assert_eq!(reader.streaminfo().sample_rate, RATE);
+        assert_eq!(reader.streaminfo().channels, 2);
+        let decoded: Vec<i32> = reader.samples().map(Result::unwrap).collect();
+        let expected: Vec<i32> = hound::WavReader::new(Cursor::new(wav))
+            .unwrap()
+            .samples::<i16>()
+            .map(|sample| i32::from(sample.unwrap()))
+            .collect();
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn buckets_peaks_across_channels() {
+        let clip = AudioClip::from_samples(
+            vec![0.1, -0.2, 0.0, 0.0, -0.9, 0.3, 0.05, 0.0, 0.0, 0.4],
+            RATE,
+            2,
+        );
+        assert_eq!(clip.peaks(2), vec![0.2, 0.9]);
+        assert_eq!(clip.peaks(100).len(), 5);
+        assert!(AudioClip::from_samples(Vec::new(), RATE, 1)
+            .peaks(100)
+            .is_empty());
+    }
+
+    #[test]
+    fn silence_is_not_speech() {
+        let clip = AudioClip::from_samples(vec![0.001; RATE as usize * 3], RATE, 1);
+        assert!(!clip.looks_like_speech());
+        assert!(!AudioClip::from_samples(Vec::new(), RATE, 1).looks_like_speech());
+    }
+
+    #[test]
+    fn voiced_bursts_with_pauses_look_like_speech() {
+        let mut samples = Vec::new();
+        for _ in 0..12 {
+            samples.extend(tone(0.4, 180.0, 0.3));
+            samples.extend(vec![0.0; (RATE as f32 * 0.25) as usize]);
+        }
+        assert!(AudioClip::from_samples(samples, RATE, 1).looks_like_speech());
+    }
+
+    #[test]
+    fn continuous_tone_and_noise_are_flagged() {
+        let music = AudioClip::from_samples(tone(8.0, 440.0, 0.3), RATE, 1);
+        assert!(!music.looks_like_speech());
+
+        let mut seed = 1u32;
+        let noise: Vec<f32> = (0..RATE as usize * 2)
+            .map(|i| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let value = (seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5;
+                // Gaps keep the activity ratio speech-like so only the
+                // zero-crossing rate can reject it.
+                if (i / 4_000) % 2 == 0 {
+                    value
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        assert!(!AudioClip::from_samples(noise, RATE, 1).looks_like_speech());
+    }
+}
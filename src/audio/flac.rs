@@ -0,0 +1,299 @@
+//! Minimal FLAC encoder for [`super::AudioClip::to_flac`]: fixed-size
+//! blocks, independent channels and the format's fixed predictors with
+//! Rice-coded residuals. It compresses speech well enough for archiving
+//! without pulling in a native library.
+
+const BLOCK_SIZE: usize = 4096;
+/// Largest parameter the 4-bit Rice coding method can express; 15 is the
+/// escape code.
+const MAX_RICE_PARAMETER: u32 = 14;
+
+/// Encodes interleaved integer samples of `bits` bits (16 or 24).
+pub(crate) fn encode(samples: &[i32], sample_rate: u32, channels: u16, bits: u32) -> Vec<u8> {
+    let channel_count = usize::from(channels.max(1));
+    let frames = samples.len() / channel_count;
+
+    let mut out = BitWriter::default();
+    out.bytes.extend_from_slice(b"fLaC");
+    // STREAMINFO, the only (and so last) metadata block.
+    out.write(1, 1);
+    out.write(0, 7);
+    out.write(34, 24);
+    // Minimum and maximum block size; the shorter last block does not count.
+    out.write(BLOCK_SIZE as u64, 16);
+    out.write(BLOCK_SIZE as u64, 16);
+    out.write(0, 24);
+    out.write(0, 24);
+    out.write(u64::from(sample_rate), 20);
+    out.write(channel_count as u64 - 1, 3);
+    out.write(u64::from(bits) - 1, 5);
+    out.write(frames as u64, 36);
+    // An all-zero MD5 signature means "not computed".
+    out.bytes.extend_from_slice(&[0; 16]);
+
+    let mut channel = Vec::with_capacity(BLOCK_SIZE);
+    for (index, block) in samples[..frames * channel_count]
+        .chunks(BLOCK_SIZE * channel_count)
+        .enumerate()
+    {
+        let block_len = block.len() / channel_count;
+        let mut frame = BitWriter::default();
+        frame.write(0b1111_1111_1111_1000, 16);
+        // Block size as a 16-bit value at the end of the header; sample rate
+        // and depth from STREAMINFO except for the depth code.
+        frame.write(0b0111, 4);
+        frame.write(0b0000, 4);
+        frame.write(channel_count as u64 - 1, 4);
+        frame.write(if bits == 24 { 0b110 } else { 0b100 }, 3);
+        frame.write(0, 1);
+        write_utf8_number(&mut frame, index as u64);
+        frame.write(block_len as u64 - 1, 16);
+        let crc = crc8(&frame.bytes);
+        frame.write(u64::from(crc), 8);
+
+        for ch in 0..channel_count {
+            channel.clear();
+            channel.extend(
+                block
+                    .iter()
+                    .skip(ch)
+                    .step_by(channel_count)
+                    .map(|s| *s as i64),
+            );
+            write_subframe(&mut frame, &channel, bits);
+        }
+        frame.align();
+        let crc = crc16(&frame.bytes);
+        frame.write(u64::from(crc), 16);
+        out.bytes.extend_from_slice(&frame.bytes);
+    }
+    out.bytes
+}
+
+fn write_subframe(out: &mut BitWriter, samples: &[i64], bits: u32) {
+    if samples.iter().all(|sample| *sample == samples[0]) {
+        out.write(0b0000_0000, 8);
+        out.write_signed(samples[0], bits);
+        return;
+    }
+    let verbatim_bits = samples.len() as u64 * u64::from(bits);
+    let best = (0..=4usize.min(samples.len() - 1))
+        .map(|order| {
+            let residuals = fixed_residuals(samples, order);
+            let (parameter, size) = best_rice_parameter(&residuals);
+            (
+                order,
+                residuals,
+                parameter,
+                size + order as u64 * u64::from(bits),
+            )
+        })
+        .min_by_key(|(_, _, _, size)| *size);
+    match best {
+        Some((order, residuals, parameter, size)) if size < verbatim_bits => {
+            out.write(0b0001_0000 | ((order as u64) << 1), 8);
+            for warmup in &samples[..order] {
+                out.write_signed(*warmup, bits);
+            }
+            // Rice method with 4-bit parameters, a single partition.
+            out.write(0b00, 2);
+            out.write(0, 4);
+            out.write(u64::from(parameter), 4);
+            for residual in residuals {
+                out.write_rice(zigzag(residual), parameter);
+            }
+        }
+        _ => {
+            out.write(0b0000_0010, 8);
+            for sample in samples {
+                out.write_signed(*sample, bits);
+            }
+        }
+    }
+}
+
+/// Residuals after the FLAC fixed predictor of `order` (0–4).
+fn fixed_residuals(samples: &[i64], order: usize) -> Vec<i64> {
+    (order..samples.len())
+        .map(|i| {
+            let s = |back: usize| samples[i - back];
+            let prediction = match order {
+                0 => 0,
+                1 => s(1),
+                2 => 2 * s(1) - s(2),
+                3 => 3 * s(1) - 3 * s(2) + s(3),
+                _ => 4 * s(1) - 6 * s(2) + 4 * s(3) - s(4),
+            };
+            samples[i] - prediction
+        })
+        .collect()
+}
+
+/// The Rice parameter giving the fewest bits, and that size.
+fn best_rice_parameter(residuals: &[i64]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAMETER)
+        .map(|parameter| {
+            let size = residuals
+                .iter()
+                .map(|residual| (zigzag(*residual) >> parameter) + 1 + u64::from(parameter))
+                .sum::<u64>();
+            (parameter, size)
+        })
+        .min_by_key(|(_, size)| *size)
+        .unwrap_or((0, 0))
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Frame numbers use the same variable-length coding as UTF-8.
+fn write_utf8_number(out: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        out.write(value, 8);
+        return;
+    }
+    let mut continuation = 1;
+    // Each continuation byte holds 6 bits and takes one from the lead byte.
+    while value >= 1 << (5 * continuation + 6) {
+        continuation += 1;
+    }
+    let lead_marker = (0xFF00u64 >> (continuation + 1)) & 0xFF;
+    out.write(lead_marker | (value >> (6 * continuation)), 8);
+    for shift in (0..continuation).rev() {
+        out.write(0x80 | ((value >> (6 * shift)) & 0x3F), 8);
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |mut crc, byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |mut crc, byte| {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    pending: u64,
+    pending_bits: u32,
+}
+
+impl BitWriter {
+    /// Appends the low `bits` bits of `value`, most significant first.
+    fn write(&mut self, value: u64, bits: u32) {
+        for bit in (0..bits).rev() {
+            self.pending = (self.pending << 1) | ((value >> bit) & 1);
+            self.pending_bits += 1;
+            if self.pending_bits == 8 {
+                self.bytes.push(self.pending as u8);
+                self.pending = 0;
+                self.pending_bits = 0;
+            }
+        }
+    }
+
+    fn write_signed(&mut self, value: i64, bits: u32) {
+        self.write(value as u64 & ((1 << bits) - 1), bits);
+    }
+
+    fn write_rice(&mut self, value: u64, parameter: u32) {
+        for _ in 0..value >> parameter {
+            self.write(0, 1);
+        }
+        self.write(1, 1);
+        self.write(value, parameter);
+    }
+
+    /// Pads with zero bits to the next byte boundary.
+    fn align(&mut self) {
+        if self.pending_bits > 0 {
+            self.write(0, 8 - self.pending_bits);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(bytes: &[u8]) -> (claxon::metadata::StreamInfo, Vec<i32>) {
+        let mut reader = claxon::FlacReader::new(std::io::Cursor::new(bytes)).unwrap();
+        let info = reader.streaminfo();
+        let samples = reader.samples().map(Result::unwrap).collect();
+        (info, samples)
+    }
+
+    #[test]
+    fn round_trips_losslessly() {
+        // Stereo speech-like signal across several blocks plus a short tail,
+        // with a silent stretch for the constant subframe and noise for the
+        // verbatim one.
+        let mut seed = 1u32;
+        let mut noise = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 8) as i32 % 30_000
+        };
+        let frames = BLOCK_SIZE * 3 + 123;
+        let samples: Vec<i32> = (0..frames)
+            .flat_map(|i| {
+                let t = i as f32 / 16_000.0;
+                let left = match i {
+                    _ if i < BLOCK_SIZE => 0,
+                    _ if i < 2 * BLOCK_SIZE => noise(),
+                    _ => ((t * 440.0 * std::f32::consts::TAU).sin() * 20_000.0) as i32,
+                };
+                [left, (left / 3).saturating_sub(7)]
+            })
+            .collect();
+        let bytes = encode(&samples, 16_000, 2, 16);
+        let (info, decoded) = decode(&bytes);
+        assert_eq!(info.sample_rate, 16_000);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.bits_per_sample, 16);
+        assert_eq!(info.samples, Some(frames as u64));
+        assert_eq!(decoded, samples);
+
+        let wide: Vec<i32> = (0..5_000)
+            .map(|i| (i * 1_543) % 8_000_000 - 4_000_000)
+            .collect();
+        let (info, decoded) = decode(&encode(&wide, 48_000, 1, 24));
+        assert_eq!(info.bits_per_sample, 24);
+        assert_eq!(decoded, wide);
+    }
+
+    #[test]
+    fn numbers_frames_like_utf8() {
+        for value in [0u64, 0x7F, 0x80, 0x7FF, 0x800, 0xFFFF, 0x1_0000, 0x3FF_FFFF] {
+            let mut out = BitWriter::default();
+            write_utf8_number(&mut out, value);
+            if let Some(c) = char::from_u32(value as u32) {
+                let mut utf8 = [0; 4];
+                assert_eq!(out.bytes, c.encode_utf8(&mut utf8).as_bytes());
+            } else {
+                assert_eq!(out.bytes[0] >> 2, 0b11_1110);
+            }
+        }
+    }
+}
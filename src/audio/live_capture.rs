@@ -2,15 +2,17 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{mpsc, Arc};
 use std::thread;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{FromSample, Sample as SampleExt};
 use parking_lot::Mutex;
 use tokio::sync::mpsc as tokio_mpsc;
 
+use crate::audio::capture_failure;
 use crate::error::AppError;
 use crate::realtime::audio::{
-    base64_pcm16, chunk_pcm16, downmix_to_mono, pcm16_le, resample_linear, TARGET_SAMPLE_RATE,
+    base64_pcm16, chunk_pcm16, downmix_to_mono, pcm16_le, resample_linear, HighPass,
+    TARGET_SAMPLE_RATE,
 };
 use crate::realtime::events::RealtimeEvent;
 
@@ -22,19 +24,33 @@ pub struct LiveCapture {
     worker: Option<thread::JoinHandle<()>>,
     sample_tx: Option<mpsc::SyncSender<Vec<f32>>>,
     level_bits: Arc<AtomicU32>,
-    error_flag: Arc<Mutex<Option<String>>>,
+    error_flag: Arc<Mutex<Option<CaptureError>>>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+/// A failure reported by the input stream while capturing.
+#[derive(Debug, Clone)]
+pub struct CaptureError {
+    pub message: String,
+    /// The device went away (e.g. unplugged); no more audio will arrive.
+    pub device_lost: bool,
 }
 
 #[derive(Clone)]
 struct CaptureConfig {
     sample_rate: u32,
     channels: u16,
+    high_pass_hz: Option<f32>,
 }
 
 impl LiveCapture {
+    /// Starts capturing from the default input device. With `high_pass_hz`
+    /// set, the streamed audio is high-pass filtered at that frequency.
     pub fn start(
         audio_tx: tokio_mpsc::Sender<String>,
         event_tx: mpsc::Sender<RealtimeEvent>,
+        high_pass_hz: Option<f32>,
     ) -> Result<Self, AppError> {
         let host = cpal::default_host();
         let device = host
@@ -44,14 +60,16 @@ impl LiveCapture {
         let sample_format = supported.sample_format();
         let sample_rate = supported.sample_rate().0;
         let config: cpal::StreamConfig = supported.into();
+        let channels = config.channels;
         let capture_config = CaptureConfig {
             sample_rate,
-            channels: config.channels,
+            channels,
+            high_pass_hz,
         };
 
         let (sample_tx, sample_rx) = mpsc::sync_channel(SAMPLE_QUEUE_CAPACITY);
         let level_bits = Arc::new(AtomicU32::new(0));
-        let error_flag = Arc::new(Mutex::new(None::<String>));
+        let error_flag = Arc::new(Mutex::new(None::<CaptureError>));
 
         let worker_events = event_tx.clone();
         let worker = thread::spawn(move || {
@@ -69,8 +87,7 @@ impl LiveCapture {
         )?;
         stream
             .play()
-            .context("Failed to start live audio stream")
-            .map_err(AppError::from)?;
+            .map_err(|err| capture_failure("Failed to start live audio stream", err))?;
 
         Ok(Self {
             stream: Some(stream),
@@ -78,14 +95,22 @@ impl LiveCapture {
             sample_tx: Some(sample_tx),
             level_bits,
             error_flag,
+            sample_rate,
+            channels,
         })
     }
 
+    /// Sample rate and channel count negotiated with the input device, before
+    /// conversion to the 24 kHz mono stream.
+    pub fn input_format(&self) -> (u32, u16) {
+        (self.sample_rate, self.channels)
+    }
+
     pub fn current_level(&self) -> f32 {
         f32::from_bits(self.level_bits.load(Ordering::Relaxed))
     }
 
-    pub fn take_error(&self) -> Option<String> {
+    pub fn take_error(&self) -> Option<CaptureError> {
         self.error_flag.lock().take()
     }
 
@@ -109,8 +134,7 @@ impl Drop for LiveCapture {
 fn choose_input_config(device: &cpal::Device) -> Result<cpal::SupportedStreamConfig, AppError> {
     let supported_configs = device
         .supported_input_configs()
-        .context("Failed to query device capabilities")
-        .map_err(AppError::from)?;
+        .map_err(|err| capture_failure("Failed to query device capabilities", err))?;
 
     let desired_sample_rate = cpal::SampleRate(TARGET_SAMPLE_RATE);
     let mut mono_exact = None;
@@ -148,7 +172,7 @@ fn build_live_stream(
     config: &cpal::StreamConfig,
     sample_tx: mpsc::SyncSender<Vec<f32>>,
     level_bits: Arc<AtomicU32>,
-    error_flag: Arc<Mutex<Option<String>>>,
+    error_flag: Arc<Mutex<Option<CaptureError>>>,
     event_tx: mpsc::Sender<RealtimeEvent>,
 ) -> Result<cpal::Stream, AppError> {
     let stream = match sample_format {
@@ -188,8 +212,7 @@ fn build_live_stream(
             )))
         }
     }
-    .context("Failed to build live input stream")
-    .map_err(AppError::from)?;
+    .map_err(|err| capture_failure("Failed to build live input stream", err))?;
 
     Ok(stream)
 }
@@ -231,10 +254,16 @@ fn audio_worker(
 ) {
     let chunk_samples = ((TARGET_SAMPLE_RATE * AUDIO_CHUNK_MS) / 1000).max(1) as usize;
     let mut pending = Vec::<f32>::with_capacity(chunk_samples * 2);
+    let mut filter = config
+        .high_pass_hz
+        .map(|cutoff| HighPass::new(cutoff, TARGET_SAMPLE_RATE));
 
     while let Ok(samples) = sample_rx.recv() {
         let mono = downmix_to_mono(&samples, config.channels);
-        let resampled = resample_linear(&mono, config.sample_rate, TARGET_SAMPLE_RATE);
+        let mut resampled = resample_linear(&mono, config.sample_rate, TARGET_SAMPLE_RATE);
+        if let Some(filter) = filter.as_mut() {
+            filter.process(&mut resampled);
+        }
         pending.extend(resampled);
 
         while pending.len() >= chunk_samples {
@@ -263,8 +292,11 @@ fn audio_worker(
     });
 }
 
-fn capture_error(err: cpal::StreamError, flag: &Arc<Mutex<Option<String>>>) {
-    *flag.lock() = Some(err.to_string());
+fn capture_error(err: cpal::StreamError, flag: &Arc<Mutex<Option<CaptureError>>>) {
+    *flag.lock() = Some(CaptureError {
+        device_lost: matches!(err, cpal::StreamError::DeviceNotAvailable),
+        message: err.to_string(),
+    });
 }
 
 #[cfg(test)]
@@ -287,6 +319,7 @@ mod tests {
         let config = CaptureConfig {
             sample_rate: TARGET_SAMPLE_RATE,
             channels: 1,
+            high_pass_hz: None,
         };
 
         let handle = thread::spawn(move || audio_worker(config, sample_rx, audio_tx, event_tx));
@@ -309,6 +342,7 @@ mod tests {
         let config = CaptureConfig {
             sample_rate: 48_000,
             channels: 2,
+            high_pass_hz: Some(80.0),
         };
 
         let handle = thread::spawn(move || audio_worker(config, sample_rx, audio_tx, event_tx));
@@ -1,9 +1,67 @@
 mod clip;
+#[cfg(feature = "flac")]
+mod flac;
 mod live_capture;
 mod player;
-#[allow(dead_code)]
 mod recorder;
+mod slicer;
 
-pub use clip::AudioClip;
-pub use live_capture::LiveCapture;
+pub use clip::{AudioClip, WavDepth};
+pub use live_capture::{CaptureError, LiveCapture};
 pub use player::AudioPlayer;
+pub use recorder::{Recorder, RecordingPreset};
+pub use slicer::{find_cut, SliceConfig};
+
+use crate::error::AppError;
+
+/// Whether a capture error reads like the system refusing microphone access
+/// (macOS privacy settings, or device permissions elsewhere) rather than a
+/// missing or broken device. Backends word this differently, so this is a
+/// best-effort match on the message.
+pub fn is_permission_error(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    [
+        "permission",
+        "not permitted",
+        "not authorized",
+        "unauthorized",
+        "access denied",
+        "access is denied",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
+/// Error for a failed capture step, telling permission problems apart.
+fn capture_failure(context: &str, err: impl std::fmt::Display) -> AppError {
+    let message = format!("{context}: {err}");
+    if is_permission_error(&message) {
+        AppError::MicrophoneDenied(message)
+    } else {
+        AppError::Audio(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_permission_failures() {
+        assert!(is_permission_error(
+            "A backend-specific error has occurred: Permission denied (os error 13)"
+        ));
+        assert!(is_permission_error("The operation is not authorized"));
+        assert!(!is_permission_error(
+            "The requested device is no longer available"
+        ));
+        assert!(matches!(
+            capture_failure("Failed to start audio stream", "Operation not permitted"),
+            AppError::MicrophoneDenied(_)
+        ));
+        assert!(matches!(
+            capture_failure("Failed to start audio stream", "device busy"),
+            AppError::Audio(_)
+        ));
+    }
+}
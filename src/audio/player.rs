@@ -1,4 +1,5 @@
 use std::io::Cursor;
+use std::ops::Range;
 use std::time::{Duration, Instant};
 
 use crate::audio::AudioClip;
@@ -8,12 +9,19 @@ pub struct AudioPlayer {
     _stream: rodio::OutputStream,
     handle: rodio::OutputStreamHandle,
     current: Option<PlaybackHandle>,
+    looping: bool,
+    /// A-B repeat region; while set, only this part of the clip is played,
+    /// over and over.
+    region: Option<Range<Duration>>,
 }
 
 pub struct PlaybackHandle {
     clip: AudioClip,
     sink: rodio::Sink,
     started: Instant,
+    /// Clip position at which the audio queued at `started` begins.
+    offset: Duration,
+    streaming: bool,
 }
 
 impl AudioPlayer {
@@ -24,10 +32,16 @@ impl AudioPlayer {
             _stream: stream,
             handle,
             current: None,
+            looping: false,
+            region: None,
         })
     }
 
+    /// Plays `clip`, replacing whatever is currently playing and clearing any
+    /// A-B region.
     pub fn play(&mut self, mut clip: AudioClip) -> Result<(), AppError> {
+        self.stop();
+        self.region = None;
         let wav_bytes = clip.wav_bytes()?;
         let cursor = Cursor::new((*wav_bytes).clone());
         let decoder = rodio::Decoder::new(cursor)
@@ -40,35 +54,130 @@ impl AudioPlayer {
             clip,
             sink,
             started: Instant::now(),
+            offset: Duration::ZERO,
+            streaming: false,
         });
         Ok(())
     }
 
+    /// Starts playback of a clip whose remaining audio will arrive through
+    /// `append_stream`. The playback stays active until `finish_stream` is
+    /// called, even if the sink briefly runs dry between chunks. Like `play`,
+    /// it replaces the current playback.
+    pub fn play_stream(&mut self, clip: AudioClip) -> Result<(), AppError> {
+        self.stop();
+        self.region = None;
+        let sink = rodio::Sink::try_new(&self.handle)
+            .map_err(|err| AppError::Audio(format!("Audio sink error: {err}")))?;
+        sink.append(rodio::buffer::SamplesBuffer::new(
+            clip.channels,
+            clip.sample_rate,
+            clip.samples().to_vec(),
+        ));
+        sink.play();
+        self.current = Some(PlaybackHandle {
+            clip,
+            sink,
+            started: Instant::now(),
+            offset: Duration::ZERO,
+            streaming: true,
+        });
+        Ok(())
+    }
+
+    pub fn append_stream(&mut self, clip: &AudioClip) -> Result<(), AppError> {
+        let Some(playback) = self.current.as_mut().filter(|playback| playback.streaming) else {
+            return Err(AppError::Audio("No streaming playback in progress".into()));
+        };
+        if clip.sample_rate != playback.clip.sample_rate || clip.channels != playback.clip.channels
+        {
+            return Err(AppError::Audio(
+                "Streamed audio chunk format changed mid-playback".into(),
+            ));
+        }
+        playback.sink.append(rodio::buffer::SamplesBuffer::new(
+            clip.channels,
+            clip.sample_rate,
+            clip.samples().to_vec(),
+        ));
+        playback.clip.append_samples(clip.samples());
+        Ok(())
+    }
+
+    pub fn finish_stream(&mut self) {
+        if let Some(playback) = self.current.as_mut() {
+            playback.streaming = false;
+        }
+    }
+
     pub fn stop(&mut self) {
         if let Some(playback) = self.current.take() {
             playback.sink.stop();
         }
     }
 
-    pub fn refresh(&mut self) {
-        if let Some(handle) = &self.current {
-            if handle.sink.empty() {
-                self.current = None;
+    /// When on, a finished clip starts over from the beginning. Turning it
+    /// off lets the current pass play out; `stop` always halts at once.
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    /// Repeats `region` of the current clip until cleared. Setting it jumps
+    /// to its start; clearing it lets playback run on past its end. Ignored
+    /// while audio is still streaming in.
+    pub fn set_region(&mut self, region: Option<Range<Duration>>) -> Result<(), AppError> {
+        let Some(handle) = self.current.as_mut().filter(|handle| !handle.streaming) else {
+            self.region = region;
+            return Ok(());
+        };
+        match &region {
+            Some(region) => {
+                handle.sink = rodio::Sink::try_new(&self.handle)
+                    .map_err(|err| AppError::Audio(format!("Audio sink error: {err}")))?;
+                handle.queue(region.clone());
+            }
+            None => {
+                if let Some(old) = self.region.take() {
+                    handle.queue(old.end..handle.clip.duration());
+                }
             }
         }
+        self.region = region;
+        Ok(())
+    }
+
+    pub fn region(&self) -> Option<Range<Duration>> {
+        self.region.clone()
+    }
+
+    pub fn refresh(&mut self) {
+        let Some(handle) = self.current.as_mut() else {
+            return;
+        };
+        if !handle.sink.empty() || handle.streaming {
+            return;
+        }
+        if let Some(region) = &self.region {
+            handle.queue(region.clone());
+        } else if self.looping && !handle.clip.samples().is_empty() {
+            handle.queue(Duration::ZERO..handle.clip.duration());
+        } else {
+            self.current = None;
+        }
     }
 
     pub fn is_playing(&self) -> bool {
         self.current
             .as_ref()
-            .map(|handle| !handle.sink.empty())
+            .map(|handle| handle.streaming || !handle.sink.empty())
             .unwrap_or(false)
     }
 
+    /// Current position in the clip.
     pub fn elapsed(&self) -> Duration {
         self.current
             .as_ref()
-            .map(|handle| handle.started.elapsed())
+            .map(PlaybackHandle::position)
             .unwrap_or_default()
     }
 
@@ -82,7 +191,70 @@ impl AudioPlayer {
     pub fn level(&self) -> f32 {
         self.current
             .as_ref()
-            .map(|handle| handle.clip.level_at(handle.started.elapsed()))
+            .map(|handle| handle.clip.level_at(handle.position()))
             .unwrap_or(0.0)
     }
 }
+
+impl PlaybackHandle {
+    fn position(&self) -> Duration {
+        (self.offset + self.started.elapsed()).min(self.clip.duration())
+    }
+
+    /// Appends the `range` part of the clip to the sink, restarting the
+    /// position clock when nothing else is queued.
+    fn queue(&mut self, range: Range<Duration>) {
+        let samples = sample_range(&self.clip, range.clone());
+        if samples.is_empty() {
+            return;
+        }
+        if self.sink.empty() {
+            self.started = Instant::now();
+            self.offset = range.start;
+        }
+        self.sink.append(rodio::buffer::SamplesBuffer::new(
+            self.clip.channels,
+            self.clip.sample_rate,
+            self.clip.samples()[samples].to_vec(),
+        ));
+    }
+}
+
+/// Sample indices of `range` in `clip`, aligned to whole frames.
+fn sample_range(clip: &AudioClip, range: Range<Duration>) -> Range<usize> {
+    let channels = clip.channels.max(1) as usize;
+    let index = |at: Duration| {
+        let frame = (at.as_secs_f64() * clip.sample_rate as f64) as usize;
+        (frame * channels).min(clip.samples().len())
+    };
+    let start = index(range.start);
+    start..index(range.end).max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn region_maps_to_whole_frames() {
+        let clip = AudioClip::from_samples(vec![0.0; 2_000], 1_000, 2);
+        assert_eq!(
+            sample_range(
+                &clip,
+                Duration::from_millis(250)..Duration::from_millis(500)
+            ),
+            500..1_000
+        );
+        assert_eq!(
+            sample_range(&clip, Duration::from_millis(900)..Duration::from_secs(5)),
+            1_800..2_000
+        );
+        assert_eq!(
+            sample_range(
+                &clip,
+                Duration::from_millis(500)..Duration::from_millis(100)
+            ),
+            1_000..1_000
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -6,13 +9,44 @@ use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{FromSample, Sample as SampleExt};
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 
-use crate::audio::AudioClip;
+use crate::audio::{capture_failure, find_cut, AudioClip, SliceConfig};
 use crate::error::AppError;
 
 pub struct Recorder {
     handle: Option<RecorderHandle>,
     last_error: Option<String>,
+    preset: RecordingPreset,
+    cache_dir: Option<PathBuf>,
+    negotiated: Option<(u32, u16)>,
+}
+
+/// Sample rates tried in order for mono capture with [`RecordingPreset::Voice`].
+pub const PREFERRED_SAMPLE_RATES: [u32; 3] = [16_000, 48_000, 44_100];
+
+/// Capture format the recorder asks the input device for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingPreset {
+    /// 16 kHz mono, enough for speech recognition.
+    #[default]
+    Voice,
+    /// 48 kHz stereo.
+    HighQuality,
+    /// The highest sample rate the device offers, in its own channel layout.
+    MatchDevice,
+}
+
+impl RecordingPreset {
+    /// Preferred (sample rate, channel count), or `None` to take the device's best.
+    fn target(self) -> Option<(u32, u16)> {
+        match self {
+            RecordingPreset::Voice => Some((16_000, 1)),
+            RecordingPreset::HighQuality => Some((48_000, 2)),
+            RecordingPreset::MatchDevice => None,
+        }
+    }
 }
 
 pub struct RecorderHandle {
@@ -21,21 +55,80 @@ pub struct RecorderHandle {
     sample_rate: u32,
     channels: u16,
     started: Instant,
+    error_flag: Arc<Mutex<Option<String>>>,
+    cache_path: Option<PathBuf>,
+    /// Frames at the start of the cache file already returned by
+    /// [`Recorder::take_slice`].
+    sliced_frames: u32,
+}
+
+// A recording that is dropped instead of stopped, e.g. one discarded as
+// too short, must not leave its cache file behind.
+impl Drop for RecorderHandle {
+    fn drop(&mut self) {
+        drop(self.stream.take());
+        if let Some(path) = self.cache_path.take() {
+            self.shared.cache.lock().take();
+            let _ = std::fs::remove_file(path);
+        }
+    }
 }
 
+type CacheWriter = hound::WavWriter<BufWriter<File>>;
+
 struct SharedBuffer {
+    /// Captured audio that is not in the cache file: everything when there
+    /// is no cache directory, else what arrived after a write to it failed.
     samples: Mutex<Vec<f32>>,
+    /// When set, samples go to this file instead of `samples`. Closed, with
+    /// what it holds kept, after a failed write.
+    cache: Mutex<Option<CacheWriter>>,
     level_bits: AtomicU32,
 }
 
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Recorder {
     pub fn new() -> Self {
         Self {
             handle: None,
             last_error: None,
+            preset: RecordingPreset::default(),
+            cache_dir: None,
+            negotiated: None,
         }
     }
 
+    pub fn with_preset(mut self, preset: RecordingPreset) -> Self {
+        self.preset = preset;
+        self
+    }
+
+    /// Takes effect the next time recording starts.
+    pub fn set_preset(&mut self, preset: RecordingPreset) {
+        self.preset = preset;
+    }
+
+    /// Writes captured audio to a temporary WAV file in `dir` while
+    /// recording instead of holding it in memory, which keeps long captures
+    /// cheap. `None` (the default) keeps samples in memory.
+    pub fn with_cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
+    }
+
+    /// Takes effect the next time recording starts.
+    pub fn set_cache_dir(&mut self, dir: Option<PathBuf>) {
+        self.cache_dir = dir;
+    }
+
+    /// Opens the default input device. Candidate formats are tried in order
+    /// (see [`PREFERRED_SAMPLE_RATES`]); when the device rejects one, the
+    /// next is tried before giving up.
     pub fn start(&mut self) -> Result<(), AppError> {
         if self.handle.is_some() {
             return Ok(());
@@ -47,45 +140,59 @@ impl Recorder {
             .ok_or_else(|| AppError::Audio("No default input device available".into()))?;
         let supported_configs = device
             .supported_input_configs()
-            .context("Failed to query device capabilities")
-            .map_err(AppError::from)?;
-
-        let desired_sample_rate = cpal::SampleRate(16_000);
-        let mut mono_exact = None;
-        let mut any_exact = None;
-        let mut mono_fallback = None;
-        let mut any_fallback = None;
-        for config in supported_configs {
-            let supports_desired = config.min_sample_rate() <= desired_sample_rate
-                && config.max_sample_rate() >= desired_sample_rate;
-
-            if config.channels() == 1 && supports_desired && mono_exact.is_none() {
-                mono_exact = Some(config.with_sample_rate(desired_sample_rate));
-            }
-            if supports_desired && any_exact.is_none() {
-                any_exact = Some(config.with_sample_rate(desired_sample_rate));
-            }
-            if config.channels() == 1 && mono_fallback.is_none() {
-                mono_fallback = Some(config.with_max_sample_rate());
-            }
-            if any_fallback.is_none() {
-                any_fallback = Some(config.with_max_sample_rate());
+            .map_err(|err| capture_failure("Failed to query device capabilities", err))?;
+        let mut last_error = AppError::Audio("No supported capture configuration available".into());
+        for supported in candidate_configs(supported_configs, self.preset) {
+            let (rate, channels) = (supported.sample_rate().0, supported.channels());
+            match self.open_stream(&device, supported) {
+                Ok(handle) => {
+                    log::info!("Recording at {rate} Hz, {channels} channel(s)");
+                    self.negotiated = Some((rate, channels));
+                    self.handle = Some(handle);
+                    return Ok(());
+                }
+                Err(err) => {
+                    log::warn!("Input device rejected {rate} Hz, {channels} channel(s): {err}");
+                    last_error = err;
+                }
             }
         }
+        Err(last_error)
+    }
+
+    /// Sample rate and channel count negotiated for the current or last
+    /// recording.
+    pub fn negotiated_format(&self) -> Option<(u32, u16)> {
+        self.negotiated
+    }
 
-        let supported = mono_exact
-            .or(any_exact)
-            .or(mono_fallback)
-            .or(any_fallback)
-            .ok_or_else(|| {
-                AppError::Audio("No supported capture configuration available".into())
-            })?;
+    fn open_stream(
+        &self,
+        device: &cpal::Device,
+        supported: cpal::SupportedStreamConfig,
+    ) -> Result<RecorderHandle, AppError> {
         let sample_format = supported.sample_format();
         let sample_rate = supported.sample_rate().0;
         let config: cpal::StreamConfig = supported.into();
 
+        let (cache, cache_path) = match &self.cache_dir {
+            Some(dir) => {
+                let path = dir.join(format!(
+                    "dictaite-recording-{}-{}.wav",
+                    std::process::id(),
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis()
+                ));
+                let writer = create_cache_writer(&path, sample_rate, config.channels)?;
+                (Some(writer), Some(path))
+            }
+            None => (None, None),
+        };
         let shared = Arc::new(SharedBuffer {
             samples: Mutex::new(Vec::new()),
+            cache: Mutex::new(cache),
             level_bits: AtomicU32::new(0),
         });
 
@@ -93,36 +200,54 @@ impl Recorder {
         let err_flag = Arc::new(Mutex::new(None::<String>));
         let err_clone = err_flag.clone();
 
-        let stream = build_input_stream(sample_format, &device, &config, shared_clone, err_clone)?;
-        stream
-            .play()
-            .context("Failed to start audio stream")
-            .map_err(AppError::from)?;
+        let stream = build_input_stream(sample_format, device, &config, shared_clone, err_clone)
+            .and_then(|stream| {
+                stream
+                    .play()
+                    .map_err(|err| capture_failure("Failed to start audio stream", err))?;
+                Ok(stream)
+            });
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                if let Some(path) = &cache_path {
+                    shared.cache.lock().take();
+                    let _ = std::fs::remove_file(path);
+                }
+                return Err(err);
+            }
+        };
 
-        self.handle = Some(RecorderHandle {
+        Ok(RecorderHandle {
             stream: Some(stream),
             shared,
             sample_rate,
             channels: config.channels,
             started: Instant::now(),
-        });
-
-        if let Some(err) = err_flag.lock().take() {
-            self.last_error = Some(err);
-        }
-
-        Ok(())
+            error_flag: err_flag,
+            cache_path,
+            sliced_frames: 0,
+        })
     }
 
+    /// Ends the recording and returns the audio not yet taken by
+    /// [`Self::take_slice`]. With a cache directory it is read back from the
+    /// temporary file, which is then removed.
     pub fn stop(&mut self) -> Result<Option<AudioClip>, AppError> {
         if let Some(mut handle) = self.handle.take() {
             if let Some(stream) = handle.stream.take() {
                 drop(stream);
             }
-            let samples = {
-                let mut guard = handle.shared.samples.lock();
-                std::mem::take(&mut *guard)
+            let mut samples = match handle.cache_path.take() {
+                Some(path) => {
+                    let cached = finish_cache_file(&handle.shared, &path)
+                        .and_then(|()| read_cache(&path, handle.sliced_frames));
+                    let _ = std::fs::remove_file(&path);
+                    cached?
+                }
+                None => Vec::new(),
             };
+            samples.append(&mut handle.shared.samples.lock());
             if samples.is_empty() {
                 return Ok(None);
             }
@@ -132,6 +257,88 @@ impl Recorder {
         Ok(None)
     }
 
+    /// Ends a recording made with a cache directory and hands over the WAV
+    /// file without loading it, so very long captures never sit in memory.
+    /// The file holds the whole recording, including audio already returned
+    /// by [`Self::take_slice`]. The caller owns the file afterwards. Returns
+    /// `None` when not recording to disk.
+    pub fn stop_to_file(&mut self) -> Result<Option<PathBuf>, AppError> {
+        if self
+            .handle
+            .as_ref()
+            .is_none_or(|handle| handle.cache_path.is_none())
+        {
+            return Ok(None);
+        }
+        let mut handle = self.handle.take().expect("checked above");
+        drop(handle.stream.take());
+        let path = handle.cache_path.take().expect("checked above");
+        finish_cache_file(&handle.shared, &path)?;
+        let spilled = std::mem::take(&mut *handle.shared.samples.lock());
+        if !spilled.is_empty() {
+            if let Err(err) = append_to_cache(&path, &spilled) {
+                log::warn!(
+                    "Dropped {} samples that did not fit in {}: {err}",
+                    spilled.len(),
+                    path.display()
+                );
+            }
+        }
+        Ok(Some(path))
+    }
+
+    /// Removes the audio captured so far up to the cut [`find_cut`] picks and
+    /// returns it, leaving the rest for the next call or [`Self::stop`].
+    /// `None` until a cut is possible. With a cache directory the audio is
+    /// read from the file, so only the part not yet sliced is held in
+    /// memory.
+    pub fn take_slice(&mut self, config: &SliceConfig) -> Option<AudioClip> {
+        let handle = self.handle.as_mut()?;
+        let mut unsliced = match &handle.cache_path {
+            Some(path) => {
+                if let Some(writer) = handle.shared.cache.lock().as_mut() {
+                    if let Err(err) = writer.flush() {
+                        log::warn!("Failed flushing recording cache: {err}");
+                    }
+                }
+                match read_cache(path, handle.sliced_frames) {
+                    Ok(samples) => samples,
+                    Err(err) => {
+                        log::warn!("{err}");
+                        return None;
+                    }
+                }
+            }
+            None => Vec::new(),
+        };
+        let from_cache = unsliced.len();
+        // Analyse a copy so the audio callback is not kept waiting; samples
+        // are only appended meanwhile, so the cut stays valid.
+        unsliced.extend_from_slice(&handle.shared.samples.lock());
+        let cut = find_cut(&unsliced, handle.sample_rate, handle.channels, config)?;
+        let channels = handle.channels.max(1) as usize;
+        handle.sliced_frames += (cut.min(from_cache) / channels) as u32;
+        if cut > from_cache {
+            handle.shared.samples.lock().drain(..cut - from_cache);
+        }
+        unsliced.truncate(cut);
+        Some(AudioClip::from_samples(
+            unsliced,
+            handle.sample_rate,
+            handle.channels,
+        ))
+    }
+
+    /// The latest stream error, e.g. after the device was unplugged. The
+    /// audio captured before the error is still returned by [`Self::stop`].
+    pub fn take_error(&mut self) -> Option<String> {
+        let stream_error = self
+            .handle
+            .as_ref()
+            .and_then(|handle| handle.error_flag.lock().take());
+        stream_error.or_else(|| self.last_error.take())
+    }
+
     pub fn current_level(&self) -> f32 {
         self.handle
             .as_ref()
@@ -147,6 +354,76 @@ impl Recorder {
     }
 }
 
+/// Formats to try in order. For [`RecordingPreset::Voice`] that is mono at
+/// each of [`PREFERRED_SAMPLE_RATES`] the device supports, followed by
+/// whatever [`choose_config`] falls back to.
+fn candidate_configs(
+    configs: impl IntoIterator<Item = cpal::SupportedStreamConfigRange>,
+    preset: RecordingPreset,
+) -> Vec<cpal::SupportedStreamConfig> {
+    let configs: Vec<_> = configs.into_iter().collect();
+    let mut candidates = Vec::new();
+    if preset == RecordingPreset::Voice {
+        for rate in PREFERRED_SAMPLE_RATES.map(cpal::SampleRate) {
+            let mono = configs.iter().find(|config| {
+                config.channels() == 1
+                    && config.min_sample_rate() <= rate
+                    && config.max_sample_rate() >= rate
+            });
+            if let Some(config) = mono {
+                candidates.push(config.with_sample_rate(rate));
+            }
+        }
+    }
+    if let Some(config) = choose_config(configs, preset) {
+        if !candidates.contains(&config) {
+            candidates.push(config);
+        }
+    }
+    candidates
+}
+
+/// Picks the first range supporting the preset's rate and channel count, then
+/// any range with that rate, then the preset's channel count at the highest
+/// rate, then whatever the device lists first.
+fn choose_config(
+    configs: impl IntoIterator<Item = cpal::SupportedStreamConfigRange>,
+    preset: RecordingPreset,
+) -> Option<cpal::SupportedStreamConfig> {
+    let Some((rate, channels)) = preset.target() else {
+        return configs
+            .into_iter()
+            .max_by_key(|config| config.max_sample_rate())
+            .map(|config| config.with_max_sample_rate());
+    };
+    let desired_sample_rate = cpal::SampleRate(rate);
+    let mut channels_exact = None;
+    let mut any_exact = None;
+    let mut channels_fallback = None;
+    let mut any_fallback = None;
+    for config in configs {
+        let supports_desired = config.min_sample_rate() <= desired_sample_rate
+            && config.max_sample_rate() >= desired_sample_rate;
+
+        if config.channels() == channels && supports_desired && channels_exact.is_none() {
+            channels_exact = Some(config.with_sample_rate(desired_sample_rate));
+        }
+        if supports_desired && any_exact.is_none() {
+            any_exact = Some(config.with_sample_rate(desired_sample_rate));
+        }
+        if config.channels() == channels && channels_fallback.is_none() {
+            channels_fallback = Some(config.with_max_sample_rate());
+        }
+        if any_fallback.is_none() {
+            any_fallback = Some(config.with_max_sample_rate());
+        }
+    }
+    channels_exact
+        .or(any_exact)
+        .or(channels_fallback)
+        .or(any_fallback)
+}
+
 fn build_input_stream(
     sample_format: cpal::SampleFormat,
     device: &cpal::Device,
@@ -193,27 +470,113 @@ fn build_input_stream(
             )));
         }
     }
-    .context("Failed to build input stream")
-    .map_err(AppError::from)?;
+    .map_err(|err| capture_failure("Failed to build input stream", err))?;
 
     Ok(stream)
 }
 
+fn create_cache_writer(
+    path: &Path,
+    sample_rate: u32,
+    channels: u16,
+) -> Result<CacheWriter, AppError> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    hound::WavWriter::create(path, spec)
+        .with_context(|| format!("Failed to create recording cache {}", path.display()))
+        .map_err(AppError::from)
+}
+
+/// Finalizes the cache writer so the WAV header reflects the samples written.
+/// A writer closed after a write error was finalized then.
+fn finish_cache_file(shared: &SharedBuffer, path: &Path) -> Result<(), AppError> {
+    match shared.cache.lock().take() {
+        Some(writer) => writer
+            .finalize()
+            .with_context(|| format!("Failed to finish recording cache {}", path.display()))
+            .map_err(AppError::from),
+        None => Ok(()),
+    }
+}
+
+/// Samples of the cache file from `from_frame` on. A frame cut short by a
+/// failed write is dropped.
+fn read_cache(path: &Path, from_frame: u32) -> Result<Vec<f32>, AppError> {
+    let context = || format!("Failed to read recording cache {}", path.display());
+    let mut reader = hound::WavReader::open(path)
+        .with_context(context)
+        .map_err(AppError::from)?;
+    let channels = reader.spec().channels.max(1) as usize;
+    if from_frame >= reader.duration() {
+        return Ok(Vec::new());
+    }
+    reader
+        .seek(from_frame)
+        .with_context(context)
+        .map_err(AppError::from)?;
+    let mut samples = reader
+        .samples::<f32>()
+        .collect::<Result<Vec<f32>, _>>()
+        .with_context(context)
+        .map_err(AppError::from)?;
+    samples.truncate(samples.len() - samples.len() % channels);
+    Ok(samples)
+}
+
+fn append_to_cache(path: &Path, samples: &[f32]) -> Result<(), AppError> {
+    let mut writer = hound::WavWriter::append(path)
+        .with_context(|| format!("Failed to reopen recording cache {}", path.display()))
+        .map_err(AppError::from)?;
+    for sample in samples {
+        writer
+            .write_sample(*sample)
+            .context("Failed writing recording cache")
+            .map_err(AppError::from)?;
+    }
+    writer
+        .finalize()
+        .context("Failed finishing recording cache")
+        .map_err(AppError::from)
+}
+
 fn on_audio_data<T>(input: &[T], shared: &Arc<SharedBuffer>)
 where
     T: cpal::Sample + SampleExt,
     f32: FromSample<T>,
 {
-    let mut max_amp = 0.0f32;
-    {
-        let mut buffer = shared.samples.lock();
-        buffer.reserve(input.len());
-        for frame in input {
-            let sample = SampleExt::to_sample::<f32>(*frame);
-            max_amp = max_amp.max(sample.abs());
-            buffer.push(sample);
+    let samples = input
+        .iter()
+        .map(|sample| SampleExt::to_sample::<f32>(*sample));
+    let max_amp = samples
+        .clone()
+        .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    let mut cache = shared.cache.lock();
+    let mut in_memory = 0;
+    if let Some(writer) = cache.as_mut() {
+        let channels = writer.spec().channels.max(1) as usize;
+        in_memory = input.len();
+        for (index, sample) in samples.clone().enumerate() {
+            if let Err(err) = writer.write_sample(sample) {
+                // A full disk must not cost the recording: close the file
+                // with what it holds and go on in memory from the start of
+                // this frame. `stop` joins the two.
+                log::warn!("Recording cache write failed, continuing in memory: {err}");
+                if let Some(writer) = cache.take() {
+                    let _ = writer.finalize();
+                }
+                in_memory = index - index % channels;
+                break;
+            }
         }
     }
+    drop(cache);
+    if in_memory < input.len() {
+        shared.samples.lock().extend(samples.skip(in_memory));
+    }
     shared
         .level_bits
         .store(max_amp.min(1.0).to_bits(), Ordering::Relaxed);
@@ -222,3 +585,183 @@ where
 fn capture_error(err: cpal::StreamError, flag: &Arc<Mutex<Option<String>>>) {
     *flag.lock() = Some(err.to_string());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(channels: u16, min: u32, max: u32) -> cpal::SupportedStreamConfigRange {
+        cpal::SupportedStreamConfigRange::new(
+            channels,
+            cpal::SampleRate(min),
+            cpal::SampleRate(max),
+            cpal::SupportedBufferSize::Unknown,
+            cpal::SampleFormat::F32,
+        )
+    }
+
+    fn chosen(
+        configs: Vec<cpal::SupportedStreamConfigRange>,
+        preset: RecordingPreset,
+    ) -> (u32, u16) {
+        let config = candidate_configs(configs, preset).remove(0);
+        (config.sample_rate().0, config.channels())
+    }
+
+    fn rates(configs: Vec<cpal::SupportedStreamConfigRange>) -> Vec<(u32, u16)> {
+        candidate_configs(configs, RecordingPreset::Voice)
+            .iter()
+            .map(|config| (config.sample_rate().0, config.channels()))
+            .collect()
+    }
+
+    #[test]
+    fn voice_tries_preferred_mono_rates_in_order() {
+        assert_eq!(
+            rates(vec![range(2, 8_000, 96_000), range(1, 8_000, 48_000)]),
+            vec![(16_000, 1), (48_000, 1), (44_100, 1)]
+        );
+        // A 44.1 kHz-only interface gets 44.1 kHz mono rather than an odd fallback.
+        assert_eq!(
+            rates(vec![range(2, 44_100, 44_100), range(1, 44_100, 44_100)]),
+            vec![(44_100, 1)]
+        );
+        // Without mono support the old fallback still applies.
+        assert_eq!(rates(vec![range(2, 48_000, 48_000)]), vec![(48_000, 2)]);
+    }
+
+    #[test]
+    fn presets_pick_their_target_format() {
+        let device = || vec![range(2, 8_000, 96_000), range(1, 8_000, 48_000)];
+        assert_eq!(chosen(device(), RecordingPreset::Voice), (16_000, 1));
+        assert_eq!(chosen(device(), RecordingPreset::HighQuality), (48_000, 2));
+        assert_eq!(chosen(device(), RecordingPreset::MatchDevice), (96_000, 2));
+    }
+
+    #[test]
+    fn cache_file_round_trips_captured_samples() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "dictaite-{}-recorder-cache.wav",
+            std::process::id()
+        ));
+        let shared = Arc::new(SharedBuffer {
+            samples: Mutex::new(Vec::new()),
+            cache: Mutex::new(Some(create_cache_writer(&path, 16_000, 1).unwrap())),
+            level_bits: AtomicU32::new(0),
+        });
+        on_audio_data(&[0.25f32, -0.5], &shared);
+        on_audio_data(&[i16::MIN], &shared);
+        assert!(shared.samples.lock().is_empty());
+
+        finish_cache_file(&shared, &path).unwrap();
+        let clip = AudioClip::from_wav_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((clip.sample_rate, clip.channels), (16_000, 1));
+        assert_eq!(clip.samples(), &[0.25, -0.5, -1.0]);
+        // Already closed: nothing left to finish.
+        assert!(finish_cache_file(&shared, &path).is_ok());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn cache_write_errors_continue_in_memory() {
+        let shared = Arc::new(SharedBuffer {
+            samples: Mutex::new(Vec::new()),
+            cache: Mutex::new(Some(
+                create_cache_writer(Path::new("/dev/full"), 16_000, 2).unwrap(),
+            )),
+            level_bits: AtomicU32::new(0),
+        });
+        // More than the writer buffers, so the disk is hit.
+        let input: Vec<f32> = (0..8_000).map(|i| i as f32 / 8_000.0).collect();
+        on_audio_data(&input, &shared);
+        assert!(shared.cache.lock().is_none());
+        let spilled = shared.samples.lock().clone();
+        assert!(!spilled.is_empty() && spilled.len().is_multiple_of(2));
+        assert!(input.ends_with(&spilled));
+
+        on_audio_data(&[0.5f32, 0.5], &shared);
+        assert_eq!(shared.samples.lock().len(), spilled.len() + 2);
+        assert!(finish_cache_file(&shared, Path::new("/dev/full")).is_ok());
+    }
+
+    #[test]
+    fn slices_are_read_from_the_cache_file() {
+        let path = std::env::temp_dir().join(format!(
+            "dictaite-{}-recorder-slices.wav",
+            std::process::id()
+        ));
+        let rate = 1_000;
+        let shared = Arc::new(SharedBuffer {
+            samples: Mutex::new(Vec::new()),
+            cache: Mutex::new(Some(create_cache_writer(&path, rate, 1).unwrap())),
+            level_bits: AtomicU32::new(0),
+        });
+        let mut recorder = Recorder {
+            handle: Some(RecorderHandle {
+                stream: None,
+                shared: Arc::clone(&shared),
+                sample_rate: rate,
+                channels: 1,
+                started: Instant::now(),
+                error_flag: Arc::new(Mutex::new(None)),
+                cache_path: Some(path.clone()),
+                sliced_frames: 0,
+            }),
+            ..Recorder::new()
+        };
+        let tone = |len: usize| -> Vec<f32> {
+            (0..len)
+                .map(|i| if i % 2 == 0 { 0.3 } else { -0.3 })
+                .collect()
+        };
+        let speech = [tone(5_000), vec![0.0; 600]].concat();
+        on_audio_data(&speech, &shared);
+        let slice = recorder.take_slice(&SliceConfig::default()).unwrap();
+        assert_eq!(slice.samples(), &speech[..5_300]);
+        assert!(recorder.take_slice(&SliceConfig::default()).is_none());
+
+        on_audio_data(&tone(1_000), &shared);
+        let rest = recorder.stop().unwrap().unwrap();
+        assert_eq!(rest.samples(), [vec![0.0; 300], tone(1_000)].concat());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn dropping_a_recording_removes_its_cache_file() {
+        let path = std::env::temp_dir().join(format!(
+            "dictaite-{}-recorder-dropped.wav",
+            std::process::id()
+        ));
+        let shared = Arc::new(SharedBuffer {
+            samples: Mutex::new(Vec::new()),
+            cache: Mutex::new(Some(create_cache_writer(&path, 16_000, 1).unwrap())),
+            level_bits: AtomicU32::new(0),
+        });
+        let recorder = Recorder {
+            handle: Some(RecorderHandle {
+                stream: None,
+                shared: Arc::clone(&shared),
+                sample_rate: 16_000,
+                channels: 1,
+                started: Instant::now(),
+                error_flag: Arc::new(Mutex::new(None)),
+                cache_path: Some(path.clone()),
+                sliced_frames: 0,
+            }),
+            ..Recorder::new()
+        };
+        on_audio_data(&[0.25f32; 64], &shared);
+        assert!(path.exists());
+        drop(recorder);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn falls_back_when_the_target_rate_is_unsupported() {
+        let device = vec![range(2, 44_100, 44_100), range(1, 44_100, 44_100)];
+        assert_eq!(chosen(device, RecordingPreset::Voice), (44_100, 1));
+        assert!(choose_config(Vec::new(), RecordingPreset::Voice).is_none());
+    }
+}
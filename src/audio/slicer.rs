@@ -0,0 +1,129 @@
+//! Cuts a growing recording at pauses, so long dictation can be transcribed
+//! slice by slice while the microphone stays open.
+
+use std::time::Duration;
+
+use super::clip::ACTIVE_FRAME_RMS;
+
+/// Length of the windows whose level decides what counts as a pause.
+const LEVEL_WINDOW: Duration = Duration::from_millis(20);
+
+/// Where [`find_cut`] may end a slice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliceConfig {
+    /// No cut is made before this much audio has been captured.
+    pub min_length: Duration,
+    /// Without a pause, the slice is cut at its quietest point before this.
+    pub max_length: Duration,
+    /// How long the level has to stay below `threshold` to count as a pause.
+    pub min_silence: Duration,
+    /// RMS level, 0.0 to 1.0, below which a window is silent.
+    pub threshold: f32,
+}
+
+impl Default for SliceConfig {
+    fn default() -> Self {
+        Self {
+            min_length: Duration::from_secs(5),
+            max_length: Duration::from_secs(30),
+            min_silence: Duration::from_millis(600),
+            threshold: ACTIVE_FRAME_RMS,
+        }
+    }
+}
+
+/// Sample index at which to end the first slice of `samples`: the middle of
+/// the first pause that ends past `min_length`, or the quietest window once
+/// `max_length` is reached without one. `None` while neither has happened.
+/// The index always falls on a frame boundary.
+pub fn find_cut(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    config: &SliceConfig,
+) -> Option<usize> {
+    let channels = channels.max(1) as usize;
+    let to_samples =
+        |duration: Duration| (sample_rate as f64 * duration.as_secs_f64()) as usize * channels;
+    let window = to_samples(LEVEL_WINDOW).max(channels);
+    let min_length = to_samples(config.min_length);
+    let max_length = to_samples(config.max_length).max(min_length + window);
+    let pause_windows = (config.min_silence.as_secs_f64() / LEVEL_WINDOW.as_secs_f64())
+        .ceil()
+        .max(1.0) as usize;
+
+    let mut pause_start = None;
+    let mut quietest: Option<(f32, usize)> = None;
+    for (index, chunk) in samples.chunks_exact(window).enumerate() {
+        let start = index * window;
+        if start >= max_length {
+            break;
+        }
+        let rms = (chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32).sqrt();
+        if rms >= config.threshold {
+            pause_start = None;
+        } else {
+            let first = *pause_start.get_or_insert(index);
+            let middle = (first + index).div_ceil(2) * window;
+            if index + 1 - first >= pause_windows && middle >= min_length {
+                return Some(middle);
+            }
+        }
+        if start >= min_length && quietest.is_none_or(|(level, _)| rms < level) {
+            quietest = Some((rms, start));
+        }
+    }
+    (samples.len() >= max_length).then(|| quietest.map_or(max_length, |(_, start)| start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 1_000;
+
+    fn tone(seconds: f32) -> Vec<f32> {
+        (0..(seconds * RATE as f32) as usize)
+            .map(|i| if i % 2 == 0 { 0.3 } else { -0.3 })
+            .collect()
+    }
+
+    #[test]
+    fn cuts_in_the_middle_of_the_first_long_enough_pause() {
+        let config = SliceConfig::default();
+        // A pause before the minimum length and one too short to count.
+        let mut samples = [tone(2.0), vec![0.0; 800], tone(4.0), vec![0.0; 300]].concat();
+        samples.extend(tone(1.0));
+        assert_eq!(find_cut(&samples, RATE, 1, &config), None);
+        let pause_start = samples.len();
+        samples.extend(vec![0.0; 600]);
+        assert_eq!(
+            find_cut(&samples, RATE, 1, &config),
+            Some(pause_start + 300)
+        );
+        samples.extend(tone(1.0));
+        assert_eq!(
+            find_cut(&samples, RATE, 1, &config),
+            Some(pause_start + 300)
+        );
+
+        let stereo: Vec<f32> = samples.iter().flat_map(|&s| [s, s]).collect();
+        assert_eq!(
+            find_cut(&stereo, RATE, 2, &config),
+            Some(2 * (pause_start + 300))
+        );
+    }
+
+    #[test]
+    fn cuts_long_speech_at_its_quietest_point() {
+        let config = SliceConfig::default();
+        let mut samples = tone(29.0);
+        assert_eq!(find_cut(&samples, RATE, 1, &config), None);
+        for sample in &mut samples[12_000..12_020] {
+            *sample *= 0.5;
+        }
+        samples.extend(tone(2.0));
+        assert_eq!(find_cut(&samples, RATE, 1, &config), Some(12_000));
+        assert_eq!(find_cut(&tone(31.0), RATE, 1, &config), Some(5_000));
+    }
+}
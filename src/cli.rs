@@ -0,0 +1,302 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use dict_ai_te::audio::{AudioClip, WavDepth};
+use dict_ai_te::constants::LANGUAGES;
+use dict_ai_te::error::AppError;
+use dict_ai_te::openai::OpenAiClient;
+use dict_ai_te::realtime::audio::RUMBLE_CUTOFF_HZ;
+use dict_ai_te::realtime::transport::{transcribe_clip, RealtimeSessionConfig};
+use dict_ai_te::settings::{debug_upload_path, load_settings};
+
+pub const USAGE: &str = "\
+Usage: dict_ai_te --cli <AUDIO_FILE> [options]
+
+Transcribes an audio file and prints the text to stdout.
+
+Options:
+  --language <CODE>       Spoken language (e.g. en, es); auto-detected if omitted.
+                          A per-file hint wins: a sidecar file next to the audio
+                          (interview.lang containing \"de\") or a code before the
+                          extension (interview.de.wav)
+  --translate-to <LANG>   Translate the transcript and print the translation
+  --tts-out <FILE>        Also write the printed text as speech (WAV) to FILE, at the
+                          WAV depth from Settings; FLAC when FILE ends in .flac
+                          (builds with the flac feature)
+  --voice <VOICE>         Voice for --tts-out (defaults to the female voice setting)
+  --force                 Transcribe even if the file does not seem to contain speech
+  -h, --help              Show this help";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    pub input: PathBuf,
+    pub language: Option<String>,
+    pub translate_to: Option<String>,
+    pub tts_out: Option<PathBuf>,
+    pub voice: Option<String>,
+    pub force: bool,
+}
+
+pub enum CliCommand {
+    Run(CliArgs),
+    Help,
+}
+
+/// Returns `Ok(None)` when `--cli` is absent so the GUI starts as usual.
+pub fn parse<I>(args: I) -> Result<Option<CliCommand>, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args: Vec<String> = args.into_iter().collect();
+    if !args.iter().any(|arg| arg == "--cli") {
+        return Ok(None);
+    }
+
+    let mut parsed = CliArgs::default();
+    let mut input = None;
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| {
+            iter.next()
+                .filter(|value| !value.starts_with("--"))
+                .ok_or_else(|| format!("{flag} needs a value"))
+        };
+        match arg.as_str() {
+            "--cli" => {}
+            "-h" | "--help" => return Ok(Some(CliCommand::Help)),
+            "--language" => parsed.language = Some(value("--language")?),
+            "--translate-to" => parsed.translate_to = Some(value("--translate-to")?),
+            "--tts-out" => parsed.tts_out = Some(PathBuf::from(value("--tts-out")?)),
+            "--voice" => parsed.voice = Some(value("--voice")?),
+            "--force" => parsed.force = true,
+            other if other.starts_with('-') => return Err(format!("Unknown option {other}")),
+            other => {
+                if input.is_some() {
+                    return Err(format!("Unexpected argument {other}"));
+                }
+                input = Some(PathBuf::from(other));
+            }
+        }
+    }
+    parsed.input = input.ok_or_else(|| "Missing audio file".to_string())?;
+    Ok(Some(CliCommand::Run(parsed)))
+}
+
+/// Runs a headless transcription and returns the process exit code.
+pub fn run(command: CliCommand) -> i32 {
+    let args = match command {
+        CliCommand::Help => {
+            println!("{USAGE}");
+            return 0;
+        }
+        CliCommand::Run(args) => args,
+    };
+    match execute(&args) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("error: {err}");
+            1
+        }
+    }
+}
+
+fn execute(args: &CliArgs) -> Result<(), AppError> {
+    let settings = load_settings();
+    let client = OpenAiClient::from_env()?.with_http_config(settings.http_config())?;
+    let bytes = fs::read(&args.input).map_err(|err| {
+        AppError::Message(format!("Failed reading {}: {err}", args.input.display()))
+    })?;
+    let mut clip = AudioClip::from_wav_bytes(bytes)?;
+    if !args.force && !clip.looks_like_speech() {
+        return Err(AppError::Message(format!(
+            "{} may not contain speech; pass --force to transcribe anyway",
+            args.input.display()
+        )));
+    }
+
+    if settings.reduce_low_frequency_noise {
+        clip = clip.high_pass(RUMBLE_CUTOFF_HZ);
+    }
+
+    let source_language = file_language_hint(&args.input).or_else(|| args.language.clone());
+    let config = RealtimeSessionConfig {
+        api_key: client.api_key().to_string(),
+        prompt: settings.transcription_prompt_with_hint(source_language.as_deref()),
+        source_language,
+        target_language: None,
+        upload_copy: settings.debug_save_upload.then(debug_upload_path),
+        on_completed: None,
+        http: settings.http_config(),
+    };
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| AppError::Message(format!("Failed starting runtime: {err}")))?;
+    let transcript = settings
+        .text_pipeline()
+        .apply(&runtime.block_on(transcribe_clip(config, &clip))?);
+
+    let output = match &args.translate_to {
+        Some(target) => runtime.block_on(client.translate(&transcript, &language_name(target)))?,
+        None => transcript,
+    };
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{output}").map_err(|err| AppError::Message(err.to_string()))?;
+
+    if let Some(path) = &args.tts_out {
+        let voice = args
+            .voice
+            .clone()
+            .unwrap_or_else(|| settings.female_voice.clone());
+        let audio = runtime.block_on(client.text_to_speech(&output, &voice))?;
+        let audio = speech_file_bytes(path, audio, settings.export_wav_depth)?;
+        fs::write(path, audio).map_err(|err| {
+            AppError::Message(format!("Failed writing {}: {err}", path.display()))
+        })?;
+    }
+    Ok(())
+}
+
+/// Re-encodes the synthesized WAV as FLAC when `path` asks for it, else at
+/// `depth`.
+fn speech_file_bytes(path: &Path, wav: Vec<u8>, depth: WavDepth) -> Result<Vec<u8>, AppError> {
+    let clip = AudioClip::from_wav_bytes(wav)?;
+    let flac = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("flac"));
+    if !flac {
+        // Synthesized speech has no depth of its own to keep.
+        let speech =
+            AudioClip::from_samples(clip.samples().to_vec(), clip.sample_rate(), clip.channels());
+        return speech.export_wav(depth);
+    }
+    #[cfg(feature = "flac")]
+    {
+        clip.to_flac()
+    }
+    #[cfg(not(feature = "flac"))]
+    {
+        Err(AppError::Message(
+            "FLAC output needs a build with the flac feature".into(),
+        ))
+    }
+}
+
+/// The spoken language recorded for one file: the code in a `.lang` sidecar
+/// with the same stem, else a code suffix such as `interview.de.wav`. Only
+/// codes from the built-in list count, so `report.final.wav` has no hint.
+fn file_language_hint(path: &Path) -> Option<String> {
+    let known = |code: &str| {
+        LANGUAGES
+            .iter()
+            .skip(1)
+            .find(|lang| lang.code.eq_ignore_ascii_case(code.trim()))
+            .map(|lang| lang.code.to_string())
+    };
+    let sidecar = path.with_extension("lang");
+    if sidecar != path {
+        if let Some(code) = fs::read_to_string(&sidecar)
+            .ok()
+            .and_then(|text| known(&text))
+        {
+            return Some(code);
+        }
+    }
+    Path::new(path.file_stem()?)
+        .extension()
+        .and_then(|code| known(code.to_str()?))
+}
+
+/// Accepts a language code from the built-in list as well as a free-form name.
+fn language_name(value: &str) -> String {
+    LANGUAGES
+        .iter()
+        .skip(1)
+        .find(|lang| lang.code.eq_ignore_ascii_case(value.trim()))
+        .map(|lang| lang.name.to_string())
+        .unwrap_or_else(|| value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_language_hints_from_sidecar_or_file_name() {
+        assert_eq!(
+            file_language_hint(Path::new("interview.de.wav")),
+            Some("de".into())
+        );
+        assert_eq!(
+            file_language_hint(Path::new("memo.ES.mp3")),
+            Some("es".into())
+        );
+        assert_eq!(file_language_hint(Path::new("report.final.wav")), None);
+        assert_eq!(file_language_hint(Path::new("talk.wav")), None);
+
+        let dir = std::env::temp_dir().join(format!("dictaite-{}-lang-hint", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("call.lang"), "ja\n").unwrap();
+        let hint = file_language_hint(&dir.join("call.wav"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(hint, Some("ja".into()));
+    }
+
+    #[test]
+    fn gui_starts_without_cli_flag() {
+        assert!(parse(args(&["--language", "en"])).unwrap().is_none());
+    }
+
+    #[test]
+    fn parses_all_options() {
+        let Some(CliCommand::Run(parsed)) = parse(args(&[
+            "--cli",
+            "talk.mp3",
+            "--language",
+            "es",
+            "--translate-to",
+            "en",
+            "--tts-out",
+            "out.wav",
+            "--force",
+        ]))
+        .unwrap() else {
+            panic!("expected a run command");
+        };
+        assert_eq!(parsed.input, PathBuf::from("talk.mp3"));
+        assert_eq!(parsed.language.as_deref(), Some("es"));
+        assert_eq!(parsed.translate_to.as_deref(), Some("en"));
+        assert_eq!(parsed.tts_out, Some(PathBuf::from("out.wav")));
+        assert!(parsed.force);
+    }
+
+    #[test]
+    fn writes_speech_at_the_configured_depth() {
+        let tts = AudioClip::from_samples(vec![0.25, -0.5], 24_000, 1)
+            .export_wav(WavDepth::Pcm16)
+            .unwrap();
+        let speech = AudioClip::from_wav_bytes(tts.clone()).unwrap();
+        let saved = speech_file_bytes(Path::new("out.wav"), tts, WavDepth::Float32).unwrap();
+        let clip = AudioClip::from_wav_bytes(saved).unwrap();
+        assert_eq!(clip.source_depth(), Some(WavDepth::Float32));
+        assert_eq!(clip.samples(), speech.samples());
+    }
+
+    #[test]
+    fn rejects_missing_values_and_input() {
+        assert!(parse(args(&["--cli"])).is_err());
+        assert!(parse(args(&["--cli", "a.wav", "--language"])).is_err());
+        assert!(parse(args(&["--cli", "a.wav", "--bogus"])).is_err());
+    }
+
+    #[test]
+    fn maps_language_codes_to_names() {
+        assert_eq!(language_name("de"), "Deutsch (German)");
+        assert_eq!(language_name("Klingon"), "Klingon");
+    }
+}
@@ -3,6 +3,19 @@ pub struct LanguageOption {
     pub name: &'static str,
 }
 
+impl LanguageOption {
+    /// The English name, e.g. "Spanish" for "Español (Spanish)"; used in
+    /// prompts sent to the models.
+    pub fn english_name(&self) -> &'static str {
+        let name = self
+            .name
+            .split_once('(')
+            .and_then(|(_, rest)| rest.strip_suffix(')'))
+            .unwrap_or(self.name);
+        name.split(',').next().unwrap_or(name).trim()
+    }
+}
+
 pub struct VoiceOption {
     pub id: &'static str,
     pub label: &'static str,
@@ -4,7 +4,11 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum AppError {
-    #[error("OpenAI API key is not configured")]
+    #[error(
+        "OpenAI API key is not configured: set OPENAI_API_KEY in the environment or a .env file, \
+         or store it in the OS keyring under service \"dict-ai-te\", user \"openai\" \
+         (macOS and Linux only; Windows has no keyring lookup)"
+    )]
     MissingApiKey,
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
@@ -12,8 +16,14 @@ pub enum AppError {
     Io(#[from] io::Error),
     #[error("Audio error: {0}")]
     Audio(String),
+    /// The operating system refused access to the microphone, see
+    /// [`crate::audio::is_permission_error`].
+    #[error("Microphone access denied: {0}")]
+    MicrophoneDenied(String),
     #[error("Text-to-speech error: {0}")]
     Tts(String),
+    #[error("Translation error: {0}")]
+    Translation(String),
     #[error("{0}")]
     Message(String),
 }
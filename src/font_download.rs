@@ -0,0 +1,138 @@
+//! Fetches fonts for scripts the GUI cannot draw with its built-in fonts.
+//! The files come from a configurable base URL that also serves a
+//! `SHA256SUMS` list in `sha256sum` format; each file must match it before it
+//! is stored in [`user_fonts_dir`].
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::Client;
+
+use crate::error::AppError;
+use crate::openai::HttpConfig;
+use crate::settings::config_dir;
+
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+/// Large enough for the CJK collection (about 20 MB).
+pub const MAX_FONT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Where downloaded fonts are stored; searched at startup alongside
+/// `assets/fonts/`.
+pub fn user_fonts_dir() -> PathBuf {
+    config_dir().join("fonts")
+}
+
+/// Downloads `files` from `base_url` into `dest`, verifying each against the
+/// checksum list first. Nothing is written for a file that fails; files
+/// finished before a failure are kept. Returns the paths written.
+pub async fn download_fonts(
+    http: &HttpConfig,
+    base_url: &str,
+    files: &[&str],
+    dest: &Path,
+) -> Result<Vec<PathBuf>, AppError> {
+    let base_url = base_url.trim().trim_end_matches('/');
+    if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+        return Err(AppError::Message(format!(
+            "Font download URL is not an http(s) URL: {base_url}"
+        )));
+    }
+    let client = http.build_client()?;
+    let sums = fetch(&client, http, &format!("{base_url}/{CHECKSUMS_FILE}")).await?;
+    let sums = parse_checksums(&String::from_utf8_lossy(&sums));
+
+    fs::create_dir_all(dest)?;
+    let mut written = Vec::new();
+    for file in files {
+        let expected = sums.get(*file).ok_or_else(|| {
+            AppError::Message(format!(
+                "{CHECKSUMS_FILE} at {base_url} does not list {file}"
+            ))
+        })?;
+        let bytes = fetch(&client, http, &format!("{base_url}/{file}")).await?;
+        let actual = sha256_hex(&bytes);
+        if actual != *expected {
+            return Err(AppError::Message(format!(
+                "Checksum mismatch for {file}: expected {expected}, got {actual}"
+            )));
+        }
+        let path = dest.join(file);
+        let tmp = dest.join(format!("{file}.part"));
+        fs::write(&tmp, &bytes)?;
+        fs::rename(&tmp, &path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+async fn fetch(client: &Client, http: &HttpConfig, url: &str) -> Result<Vec<u8>, AppError> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|err| http.send_error(err, "downloading fonts"))?
+        .error_for_status()?;
+    let too_large = || {
+        AppError::Message(format!(
+            "{url} is larger than {} MB",
+            MAX_FONT_BYTES / (1024 * 1024)
+        ))
+    };
+    if response
+        .content_length()
+        .is_some_and(|length| length > MAX_FONT_BYTES)
+    {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() as u64 > MAX_FONT_BYTES {
+            return Err(too_large());
+        }
+    }
+    Ok(bytes)
+}
+
+/// File name to lowercase hex digest, from `sha256sum` output. The `*` that
+/// marks binary mode is dropped.
+fn parse_checksums(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter_map(|line| {
+            let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim_start().trim_start_matches('*');
+            (hash.len() == 64 && !name.is_empty())
+                .then(|| (name.to_string(), hash.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, bytes)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sha256sum_output() {
+        let sums = parse_checksums(&format!(
+            "{}  NotoSansThai-Regular.ttf\n{} *NotoSansCJK-Regular.ttc\n\nnot a checksum line\n",
+            "AB".repeat(32),
+            "cd".repeat(32)
+        ));
+        assert_eq!(sums.len(), 2);
+        assert_eq!(sums["NotoSansThai-Regular.ttf"], "ab".repeat(32));
+        assert_eq!(sums["NotoSansCJK-Regular.ttc"], "cd".repeat(32));
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Fallback fonts for scripts beyond egui's built-in ones, and which of those
+//! scripts a text uses without a loaded font to draw it.
+
+use std::path::Path;
+
+use dict_ai_te::font_download::user_fonts_dir;
+
+/// Scripts `configure_fonts` knows a Noto font for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Cjk,
+}
+
+impl Script {
+    pub const ALL: [Self; 5] = [
+        Self::Arabic,
+        Self::Hebrew,
+        Self::Devanagari,
+        Self::Thai,
+        Self::Cjk,
+    ];
+
+    pub fn of(ch: char) -> Option<Self> {
+        match u32::from(ch) {
+            0x0590..=0x05FF | 0xFB1D..=0xFB4F => Some(Self::Hebrew),
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => {
+                Some(Self::Arabic)
+            }
+            0x0900..=0x097F => Some(Self::Devanagari),
+            0x0E00..=0x0E7F => Some(Self::Thai),
+            0x1100..=0x11FF
+            | 0x3000..=0x30FF
+            | 0x3130..=0x318F
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xFF00..=0xFFEF => Some(Self::Cjk),
+            _ => None,
+        }
+    }
+
+    /// English name, translated for display by the caller.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Arabic => "Arabic",
+            Self::Hebrew => "Hebrew",
+            Self::Devanagari => "Devanagari",
+            Self::Thai => "Thai",
+            Self::Cjk => "Chinese, Japanese or Korean",
+        }
+    }
+
+    /// The Noto font file [`configure_fonts`] loads for this script from
+    /// `assets/fonts/` or [`user_fonts_dir`].
+    pub fn font_file(self) -> &'static str {
+        match self {
+            Self::Arabic => "NotoSansArabic-Regular.ttf",
+            Self::Hebrew => "NotoSansHebrew-Regular.ttf",
+            Self::Devanagari => "NotoSansDevanagari-Regular.ttf",
+            Self::Thai => "NotoSansThai-Regular.ttf",
+            Self::Cjk => "NotoSansCJK-Regular.ttc",
+        }
+    }
+}
+
+/// Registers the Noto fallback fonts found on disk with `ctx`. Runs at
+/// startup and again after fonts are downloaded, replacing the previous set.
+pub fn configure_fonts(ctx: &egui::Context) {
+    // Start with default fonts
+    let mut fonts = egui::FontDefinitions::default();
+
+    // Helper to try load a font file from disk (system or local project)
+    let mut try_add_font = |name: &str, path: &str| {
+        if Path::new(path).exists() && !fonts.font_data.contains_key(name) {
+            if let Ok(bytes) = std::fs::read(path) {
+                fonts
+                    .font_data
+                    .insert(name.to_owned(), egui::FontData::from_owned(bytes));
+                // Add as a fallback for proportional text
+                fonts
+                    .families
+                    .entry(egui::FontFamily::Proportional)
+                    .or_default()
+                    .push(name.to_owned());
+                // And monospace too, just in case we display code/logs with these glyphs
+                fonts
+                    .families
+                    .entry(egui::FontFamily::Monospace)
+                    .or_default()
+                    .push(name.to_owned());
+                log::info!("Loaded fallback font: {name} from {path}");
+            }
+        }
+    };
+
+    // Common system locations for Noto fonts on Linux (adjust as needed for other OSes)
+    // We add several families to cover CJK, Arabic, Devanagari, Hebrew, Thai, etc.
+    let user_dir = user_fonts_dir();
+    let downloaded: Vec<(&str, String)> = Script::ALL
+        .iter()
+        .map(|script| {
+            let file = script.font_file();
+            let name = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+            (name, user_dir.join(file).display().to_string())
+        })
+        .collect();
+    for (name, path) in &downloaded {
+        try_add_font(name, path);
+    }
+
+    let candidates: &[(&str, &str)] = &[
+        // Local project assets (if you choose to vendor fonts):
+        ("NotoSans-Regular", "assets/fonts/NotoSans-Regular.ttf"),
+        (
+            "NotoSansCJK-Regular",
+            "assets/fonts/NotoSansCJK-Regular.ttc",
+        ),
+        ("NotoSansJP-Regular", "assets/fonts/NotoSansJP-Regular.otf"),
+        ("NotoSansKR-Regular", "assets/fonts/NotoSansKR-Regular.otf"),
+        ("NotoSansSC-Regular", "assets/fonts/NotoSansSC-Regular.otf"),
+        (
+            "NotoSansArabic-Regular",
+            "assets/fonts/NotoSansArabic-Regular.ttf",
+        ),
+        (
+            "NotoSansDevanagari-Regular",
+            "assets/fonts/NotoSansDevanagari-Regular.ttf",
+        ),
+        (
+            "NotoSansHebrew-Regular",
+            "assets/fonts/NotoSansHebrew-Regular.ttf",
+        ),
+        (
+            "NotoSansThai-Regular",
+            "assets/fonts/NotoSansThai-Regular.ttf",
+        ),
+        // Typical Linux paths (Ubuntu/Debian):
+        (
+            "NotoSans-Regular",
+            "/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf",
+        ),
+        (
+            "NotoSansArabic-Regular",
+            "/usr/share/fonts/truetype/noto/NotoSansArabic-Regular.ttf",
+        ),
+        (
+            "NotoSansDevanagari-Regular",
+            "/usr/share/fonts/truetype/noto/NotoSansDevanagari-Regular.ttf",
+        ),
+        (
+            "NotoSansHebrew-Regular",
+            "/usr/share/fonts/truetype/noto/NotoSansHebrew-Regular.ttf",
+        ),
+        (
+            "NotoSansThai-Regular",
+            "/usr/share/fonts/truetype/noto/NotoSansThai-Regular.ttf",
+        ),
+        // CJK often installed as TTC/OTF in these dirs:
+        (
+            "NotoSansCJK-Regular",
+            "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+        ),
+        (
+            "NotoSansJP-Regular",
+            "/usr/share/fonts/opentype/noto/NotoSansJP-Regular.otf",
+        ),
+        (
+            "NotoSansKR-Regular",
+            "/usr/share/fonts/opentype/noto/NotoSansKR-Regular.otf",
+        ),
+        (
+            "NotoSansSC-Regular",
+            "/usr/share/fonts/opentype/noto/NotoSansSC-Regular.otf",
+        ),
+    ];
+
+    for (name, path) in candidates {
+        try_add_font(name, path);
+    }
+
+    ctx.set_fonts(fonts);
+}
+
+/// Scripts used in `text` that have a character `has_glyph` rejects, in
+/// order of first appearance.
+pub fn missing_scripts(text: &str, mut has_glyph: impl FnMut(char) -> bool) -> Vec<Script> {
+    let mut missing = Vec::new();
+    for ch in text.chars().filter(|ch| !ch.is_ascii()) {
+        let Some(script) = Script::of(ch) else {
+            continue;
+        };
+        if !missing.contains(&script) && !has_glyph(ch) {
+            missing.push(script);
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_scripts_without_glyphs_once() {
+        let text = "Hola — สวัสดี, 你好, こんにちは, مرحبا, ไทย";
+        let missing = missing_scripts(text, |ch| Script::of(ch) == Some(Script::Arabic));
+        assert_eq!(missing, vec![Script::Thai, Script::Cjk]);
+        let covered_by_default = "Crème brûlée, Ελληνικά, Русский";
+        assert!(missing_scripts(covered_by_default, |_| false).is_empty());
+        assert_eq!(Script::of('한'), Some(Script::Cjk));
+        assert_eq!(Script::of('ש'), Some(Script::Hebrew));
+    }
+}
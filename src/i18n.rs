@@ -0,0 +1,985 @@
+//! Translations of the window's own text (buttons, labels, status lines).
+//! Transcripts are not affected.
+//!
+//! Keys are the English strings themselves, so an untranslated key simply
+//! shows in English. Placeholders such as `{language}` are filled in by
+//! [`t_with`].
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Selectable interface languages as (code, native name); English first.
+pub const UI_LANGUAGES: &[(&str, &str)] =
+    &[("en", "English"), ("es", "Español"), ("nl", "Nederlands")];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Switches the interface language. `None` (or an unknown code) follows the
+/// system locale, falling back to English.
+pub fn set_language(code: Option<&str>) {
+    let index = code
+        .and_then(language_index)
+        .or_else(|| system_language().and_then(|code| language_index(&code)))
+        .unwrap_or(0);
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+pub fn t(key: &'static str) -> &'static str {
+    let (code, _) = UI_LANGUAGES[CURRENT.load(Ordering::Relaxed)];
+    lookup(code, key)
+}
+
+/// Translates `key` and replaces each `{name}` placeholder with its value.
+pub fn t_with(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(t(key).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), &value.to_string())
+    })
+}
+
+fn lookup(code: &str, key: &'static str) -> &'static str {
+    tables()
+        .get(code)
+        .and_then(|table| table.get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+fn language_index(code: &str) -> Option<usize> {
+    let code = code.trim().to_ascii_lowercase();
+    UI_LANGUAGES.iter().position(|(known, _)| *known == code)
+}
+
+/// Language part of the POSIX locale variables, e.g. `nl` for `nl_NL.UTF-8`.
+fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| locale_language(&value))
+}
+
+fn locale_language(locale: &str) -> Option<String> {
+    let language = locale
+        .split(['_', '.', '@', '-'])
+        .next()?
+        .to_ascii_lowercase();
+    (!language.is_empty() && language != "c" && language != "posix").then_some(language)
+}
+
+fn tables() -> &'static HashMap<&'static str, HashMap<&'static str, &'static str>> {
+    static TABLES: OnceLock<HashMap<&'static str, HashMap<&'static str, &'static str>>> =
+        OnceLock::new();
+    TABLES.get_or_init(|| {
+        HashMap::from([
+            ("es", SPANISH.iter().copied().collect()),
+            ("nl", DUTCH.iter().copied().collect()),
+        ])
+    })
+}
+
+const SPANISH: &[(&str, &str)] = &[
+    ("Press to start listening", "Pulsa para empezar a escuchar"),
+    ("Start Listening", "Empezar a escuchar"),
+    ("Stop Listening", "Dejar de escuchar"),
+    ("Listening live...", "Escuchando en directo..."),
+    ("Translating live to {language}", "Traduciendo en directo a {language}"),
+    ("Stopped", "Detenido"),
+    ("Settings", "Ajustes"),
+    ("Profiles…", "Perfiles…"),
+    ("Profiles", "Perfiles"),
+    ("Profile:", "Perfil:"),
+    ("Switched to profile \"{name}\"", "Perfil cambiado a \"{name}\""),
+    ("Active profile: {name}", "Perfil activo: {name}"),
+    ("No profile selected", "Ningún perfil seleccionado"),
+    ("Name", "Nombre"),
+    ("Create from current settings", "Crear a partir de los ajustes actuales"),
+    ("Rename active", "Renombrar el activo"),
+    ("Delete active", "Eliminar el activo"),
+    ("Log ({count})", "Registro ({count})"),
+    ("Copy log", "Copiar registro"),
+    ("Copied error log", "Registro de errores copiado"),
+    ("Clear", "Borrar"),
+    ("Save", "Guardar"),
+    ("Copy", "Copiar"),
+    ("Play", "Reproducir"),
+    ("Stop", "Detener"),
+    ("Loop", "Repetir"),
+    ("Cancel", "Cancelar"),
+    ("Discard", "Descartar"),
+    ("Transcribe", "Transcribir"),
+    ("Transcribe from URL", "Transcribir desde URL"),
+    ("Transcribe from URL…", "Transcribir desde URL…"),
+    (
+        "Link to an audio file (WAV, MP3, FLAC or OGG)",
+        "Enlace a un archivo de audio (WAV, MP3, FLAC u OGG)",
+    ),
+    (
+        "Transcribing a URL replaces the current transcript.",
+        "Transcribir una URL reemplaza la transcripción actual.",
+    ),
+    (
+        "Downloading and transcribing audio...",
+        "Descargando y transcribiendo el audio...",
+    ),
+    ("Transcribed audio from URL", "Audio de la URL transcrito"),
+    ("Transcription failed", "La transcripción falló"),
+    ("Unsaved transcript", "Transcripción sin guardar"),
+    (
+        "Starting a new recording clears the current transcript.",
+        "Al iniciar una grabación nueva se borra la transcripción actual.",
+    ),
+    (
+        "Clearing removes the current transcript.",
+        "Al borrar se elimina la transcripción actual.",
+    ),
+    ("No speech detected", "No se detecta voz"),
+    (
+        "This clip may not contain speech — transcribe anyway?",
+        "Puede que este audio no contenga voz. ¿Transcribir de todos modos?",
+    ),
+    (
+        "Format the microphone delivered, before conversion for upload",
+        "Formato entregado por el micrófono, antes de convertirlo para el envío",
+    ),
+    ("Retry recording", "Reintentar la grabación"),
+    ("Rate limited, retrying in {seconds}s", "Límite de peticiones; reintento en {seconds} s"),
+    ("Est. cost this session: {cost}", "Coste estimado de la sesión: {cost}"),
+    ("Requests left: {remaining} of {limit}", "Solicitudes restantes: {remaining} de {limit}"),
+    ("Tokens left: {remaining} of {limit}", "Tokens restantes: {remaining} de {limit}"),
+    ("Edit", "Editar"),
+    ("Edit the transcript", "Editar la transcripción"),
+    ("Done", "Listo"),
+    ("Show paragraphs with copy buttons", "Mostrar párrafos con botones de copia"),
+    ("Nothing transcribed yet", "Aún no hay nada transcrito"),
+    ("Copy paragraph", "Copiar párrafo"),
+    ("Copied paragraph", "Párrafo copiado"),
+    ("Bilingual interleaved translation", "Traducción bilingüe intercalada"),
+    ("Recording too short", "Grabación demasiado corta"),
+    ("Shift+click copies as a single line", "Mayús+clic copia en una sola línea"),
+    ("Copy as single line", "Copiar en una sola línea"),
+    ("Write numbers as digits", "Escribir los números con cifras"),
+    ("Regenerate speech", "Regenerar voz"),
+    ("Transcript + Translation", "Transcripción + traducción"),
+    ("Save Transcript + Translation", "Guardar transcripción + traducción"),
+    (
+        "Save both panes to one .txt, .md or .json file",
+        "Guardar ambos paneles en un archivo .txt, .md o .json",
+    ),
+    ("Nothing to save yet", "Aún no hay nada que guardar"),
+    ("Text", "Texto"),
+    ("Languages", "Idiomas"),
+    ("Voices", "Voces"),
+    ("Audio input", "Entrada de audio"),
+    ("Text processing", "Procesado de texto"),
+    ("Engine and API", "Motor y API"),
+    (
+        "Synthesize again with the current voice, ignoring the cached speech",
+        "Sintetizar de nuevo con la voz actual, sin usar la voz en caché",
+    ),
+    (
+        "Translation hidden; turn translation on to see it again",
+        "Traducción oculta; activa la traducción para volver a verla",
+    ),
+    (
+        "English only: \"three hundred and twelve\" becomes 312; \"one of the\" is kept",
+        "Solo inglés: \"three hundred and twelve\" pasa a 312; \"one of the\" se mantiene",
+    ),
+    (
+        "Translate now alternates each source sentence with its translation",
+        "Traducir ahora alterna cada frase original con su traducción",
+    ),
+    (
+        "Rough estimate from the price table in settings.json",
+        "Estimación aproximada según la tabla de precios de settings.json",
+    ),
+    ("Live session error", "Error en la sesión en directo"),
+    ("Microphone error: {error}", "Error del micrófono: {error}"),
+    ("Recording stopped: microphone lost", "Grabación detenida: micrófono perdido"),
+    ("Nothing to {verb} — {pane} is empty", "Nada que {verb}: {pane} está vacía"),
+    ("copy", "copiar"),
+    ("save", "guardar"),
+    ("play", "reproducir"),
+    ("transcript", "la transcripción"),
+    ("translation", "la traducción"),
+    ("Generating speech...", "Generando voz..."),
+    ("Playing transcript ({voice})", "Reproduciendo la transcripción ({voice})"),
+    ("Previewing {voice}", "Escuchando {voice}"),
+    ("Synthesizing {segment}/{total}", "Sintetizando {segment}/{total}"),
+    ("Input: {rate} Hz · {channels} ch", "Entrada: {rate} Hz · {channels} can."),
+    ("Speech canceled", "Voz cancelada"),
+    ("Speech synthesis failed", "Ha fallado la síntesis de voz"),
+    ("Translating to {language}...", "Traduciendo a {language}..."),
+    ("Translated to {language}", "Traducido a {language}"),
+    ("Translation failed", "Ha fallado la traducción"),
+    ("Copied to clipboard", "Copiado al portapapeles"),
+    ("Copied {pane}", "Copiada {pane}"),
+    ("Transcript saved to {path}", "Transcripción guardada en {path}"),
+    ("Save Transcript", "Guardar transcripción"),
+    ("cached · {voice}", "en caché · {voice}"),
+    ("Play reuses the speech already generated", "Reproducir reutiliza la voz ya generada"),
+    (
+        "The voice or text changed; Play will generate new speech with {voice}",
+        "La voz o el texto han cambiado; Reproducir generará voz nueva con {voice}",
+    ),
+    ("Mark the start of a repeat region", "Marcar el inicio de la zona a repetir"),
+    ("Mark the end and repeat from A", "Marcar el final y repetir desde A"),
+    ("Live translation on", "Traducción en directo activada"),
+    ("Live translation off", "Traducción en directo desactivada"),
+    ("Clear the transcript and generated speech", "Borrar la transcripción y la voz generada"),
+    ("Replay the clip until stopped", "Repetir el audio hasta detenerlo"),
+    ("Stop generating speech", "Dejar de generar voz"),
+    ("Female", "Femenina"),
+    ("Male", "Masculina"),
+    ("Origin language", "Idioma de origen"),
+    ("Translate Live", "Traducir en directo"),
+    ("Toggle with Ctrl+T", "Alternar con Ctrl+T"),
+    ("Target language", "Idioma de destino"),
+    ("Translate now", "Traducir ahora"),
+    (
+        "Re-translate the edited source transcript (Ctrl+Enter)",
+        "Volver a traducir la transcripción editada (Ctrl+Intro)",
+    ),
+    ("Source transcript", "Transcripción original"),
+    ("Translated transcript", "Transcripción traducida"),
+    (
+        "Save, Copy and Play act on the selected pane",
+        "Guardar, Copiar y Reproducir actúan sobre el panel seleccionado",
+    ),
+    ("Source speech will appear here...", "Aquí aparecerá lo que digas..."),
+    ("Live translation will appear here...", "Aquí aparecerá la traducción en directo..."),
+    ("Transcribed text will appear here...", "Aquí aparecerá el texto transcrito..."),
+    ("Interface language", "Idioma de la interfaz"),
+    ("System", "Sistema"),
+    ("Default language", "Idioma predeterminado"),
+    ("Translate by default", "Traducir por defecto"),
+    ("Default target language", "Idioma de destino predeterminado"),
+    ("Female voice", "Voz femenina"),
+    ("Male voice", "Voz masculina"),
+    (
+        "Use ↑/↓ to change and Space to preview",
+        "Usa ↑/↓ para cambiar y Espacio para escuchar",
+    ),
+    ("Preview text", "Texto de prueba"),
+    ("Voice per language", "Voz por idioma"),
+    ("Remove", "Quitar"),
+    ("Add language voice", "Añadir voz por idioma"),
+    ("Engine", "Motor"),
+    ("Apply text formatting", "Aplicar formato al texto"),
+    (
+        "Tidy whitespace and drop looped sentences; turn off to keep the model's exact text",
+        "Ordena los espacios y quita frases repetidas; desactívalo para conservar el texto exacto del modelo",
+    ),
+    ("Reduce low-frequency noise", "Reducir el ruido de baja frecuencia"),
+    (
+        "Filter out rumble below 80 Hz (air conditioning, desk bumps) before transcription",
+        "Filtra el ruido por debajo de 80 Hz (aire acondicionado, golpes en la mesa) antes de transcribir",
+    ),
+    ("Model file", "Archivo del modelo"),
+    ("Browse…", "Examinar…"),
+    ("Choose Whisper Model", "Elegir modelo de Whisper"),
+    ("Recording cache folder", "Carpeta de caché de grabación"),
+    (
+        "Continuous dictation writes the recording here instead of keeping it in memory; leave empty to use memory",
+        "El dictado continuo escribe la grabación aquí en lugar de mantenerla en memoria; déjalo vacío para usar la memoria",
+    ),
+    ("Choose Recording Cache Folder", "Elegir carpeta de caché de grabación"),
+    (
+        "No network: recording for local transcription...",
+        "Sin red: grabando para transcripción local...",
+    ),
+    (
+        "Also used by the OpenAI engine when there is no network connection",
+        "También lo usa el motor de OpenAI cuando no hay conexión de red",
+    ),
+    ("Saved WAV depth", "Profundidad de los WAV guardados"),
+    (
+        "Bit depth of the WAV files written by Save speech… and --tts-out",
+        "Profundidad de bits de los WAV que escriben Guardar voz… y --tts-out",
+    ),
+    ("16-bit", "16 bits"),
+    ("24-bit", "24 bits"),
+    ("32-bit float", "32 bits en coma flotante"),
+    ("Temperature", "Temperatura"),
+    ("Label speakers", "Etiquetar hablantes"),
+    ("Chunk length (s)", "Duración de los fragmentos (s)"),
+    ("Chunk overlap (s)", "Solapamiento de los fragmentos (s)"),
+    (
+        "Long recordings are transcribed in pieces of this length",
+        "Las grabaciones largas se transcriben en fragmentos de esta duración",
+    ),
+    (
+        "Audio repeated between pieces so words at the cut are not lost",
+        "Audio repetido entre fragmentos para no perder palabras en el corte",
+    ),
+    ("Average confidence: {percent}%", "Confianza media: {percent}%"),
+    (
+        "Underlined passages are worth proofreading",
+        "Conviene revisar los pasajes subrayados",
+    ),
+    (
+        "Prefixes each speaker turn with \"Speaker 1:\" or \"Speaker 2:\"; needs a tinydiarize (tdrz) model",
+        "Antepone \"Speaker 1:\" o \"Speaker 2:\" a cada turno de palabra; requiere un modelo tinydiarize (tdrz)",
+    ),
+    (
+        "0 is deterministic and least likely to invent text on noisy audio",
+        "0 es determinista y el que menos texto inventa con audio ruidoso",
+    ),
+    ("Request timeout (s)", "Tiempo de espera de la petición (s)"),
+    ("Connect timeout (s)", "Tiempo de espera de conexión (s)"),
+    ("Proxy", "Proxy"),
+    ("Export settings…", "Exportar ajustes…"),
+    ("Import settings…", "Importar ajustes…"),
+    ("Export Settings", "Exportar ajustes"),
+    ("Import Settings", "Importar ajustes"),
+    ("Settings exported to {path}", "Ajustes exportados a {path}"),
+    ("Settings imported from {path}", "Ajustes importados de {path}"),
+    ("Export failed: {error}", "Ha fallado la exportación: {error}"),
+    ("Import failed: {error}", "Ha fallado la importación: {error}"),
+    ("Connected to live session", "Conectado a la sesión en directo"),
+    ("Audio capture stopped", "Captura de audio detenida"),
+    ("Recording for local transcription...", "Grabando para la transcripción local..."),
+    ("Transcribing locally...", "Transcribiendo localmente..."),
+    ("Transcribing locally ({progress})...", "Transcribiendo localmente ({progress})..."),
+    ("Recording may not contain speech", "Puede que la grabación no contenga voz"),
+    ("Disconnected", "Desconectado"),
+    ("Live state: {state}", "Estado en directo: {state}"),
+    ("OPENAI_API_KEY not configured", "OPENAI_API_KEY no está configurada"),
+    ("OpenAI client unavailable", "Cliente de OpenAI no disponible"),
+    ("Realtime runtime unavailable", "Entorno de ejecución en tiempo real no disponible"),
+    ("Async runtime unavailable", "Entorno de ejecución asíncrono no disponible"),
+    ("Audio output unavailable", "Salida de audio no disponible"),
+    ("Failed saving profiles: {error}", "No se pudieron guardar los perfiles: {error}"),
+    ("Clipboard error: {error}", "Error del portapapeles: {error}"),
+    ("Failed to save file: {error}", "No se pudo guardar el archivo: {error}"),
+    ("Failed to read file: {error}", "No se pudo leer el archivo: {error}"),
+    ("Transcription prompt", "Indicación de transcripción"),
+    ("Compact", "Compacto"),
+    ("Restored previous session", "Sesión anterior restaurada"),
+    ("Recover unsaved transcript", "Recuperar transcripción sin guardar"),
+    (
+        "The last session ended with transcript edits that were never saved.",
+        "La última sesión terminó con cambios en la transcripción que nunca se guardaron.",
+    ),
+    ("Restore", "Restaurar"),
+    ("Expected languages", "Idiomas esperados"),
+    (
+        "Choose two or more for recordings that switch languages",
+        "Elige dos o más para grabaciones que cambian de idioma",
+    ),
+    (
+        "Detected automatically among the expected languages",
+        "Se detecta automáticamente entre los idiomas esperados",
+    ),
+    ("Open Privacy Settings", "Abrir ajustes de privacidad"),
+    (
+        "dict-ai-te may not use the microphone. Allow it in System Settings > Privacy & Security > Microphone, then try again.",
+        "dict-ai-te no tiene permiso para usar el micrófono. Permítelo en Ajustes del Sistema > Privacidad y seguridad > Micrófono y vuelve a intentarlo.",
+    ),
+    (
+        "The system refused access to the microphone. Check that this user may record audio, then try again.",
+        "El sistema denegó el acceso al micrófono. Comprueba que este usuario puede grabar audio y vuelve a intentarlo.",
+    ),
+    ("Dismiss", "Descartar"),
+    (
+        "No installed font shows {script} text. Put {font} from the Noto fonts in assets/fonts/, then choose Reload fonts in Settings.",
+        "Ninguna fuente instalada muestra texto en {script}. Pon {font} de las fuentes Noto en assets/fonts/ y elige Recargar fuentes en Ajustes.",
+    ),
+    ("Reload fonts", "Recargar fuentes"),
+    (
+        "Loads fonts added to assets/fonts/ or the system since dict-ai-te started",
+        "Carga las fuentes añadidas a assets/fonts/ o al sistema desde que se inició dict-ai-te",
+    ),
+    ("Fonts reloaded", "Fuentes recargadas"),
+    ("Save Session as JSON", "Guardar sesión como JSON"),
+    ("Save session as JSON…", "Guardar sesión como JSON…"),
+    ("Retry", "Reintentar"),
+    (
+        "Download and transcribe the same URL again",
+        "Descargar y transcribir de nuevo la misma URL",
+    ),
+    (
+        "Translate the source transcript again",
+        "Traducir de nuevo la transcripción original",
+    ),
+    ("Show raw", "Mostrar sin formato"),
+    ("Compare with the text before formatting", "Comparar con el texto antes de aplicar el formato"),
+    ("Raw model output", "Salida sin procesar del modelo"),
+    ("Unformatted text from the transcription service", "Texto sin formato del servicio de transcripción"),
+    ("Continuous dictation", "Dictado continuo"),
+    ("Transcribe in slices cut at pauses instead of a live session", "Transcribir por fragmentos cortados en las pausas en lugar de una sesión en vivo"),
+    ("Continuous dictation needs the OpenAI engine", "El dictado continuo necesita el motor de OpenAI"),
+    ("Stop listening to switch modes", "Deja de escuchar para cambiar de modo"),
+    ("Dictating...", "Dictando..."),
+    ("Transcribing the last slice...", "Transcribiendo el último fragmento..."),
+    ("Transcribing slice {number} failed: {error}", "Falló la transcripción del fragmento {number}: {error}"),
+    (
+        "Right-click to save the whole session as JSON",
+        "Clic derecho para guardar toda la sesión como JSON",
+    ),
+    ("Arabic", "árabe"),
+    ("Hebrew", "hebreo"),
+    ("Devanagari", "devanagari"),
+    ("Thai", "tailandés"),
+    ("Chinese, Japanese or Korean", "chino, japonés o coreano"),
+    ("Font download URL", "URL de descarga de fuentes"),
+    (
+        "Folder with Noto font files and a SHA256SUMS list; enables Download fonts",
+        "Carpeta con archivos de fuentes Noto y una lista SHA256SUMS; activa Descargar fuentes",
+    ),
+    ("Download fonts", "Descargar fuentes"),
+    ("Saves the fonts to {path}", "Guarda las fuentes en {path}"),
+    ("Downloading fonts...", "Descargando fuentes..."),
+    ("Fonts installed", "Fuentes instaladas"),
+    ("Font download failed: {error}", "Falló la descarga de fuentes: {error}"),
+    ("Font download failed", "Falló la descarga de fuentes"),
+    ("Paragraphs", "Párrafos"),
+    ("Blank lines only", "Solo líneas en blanco"),
+    ("Group sentences", "Agrupar frases"),
+    (
+        "Group sentences starts a new paragraph every few sentences, for transcripts with a sentence on every line",
+        "Agrupar frases empieza un párrafo nuevo cada pocas frases, para transcripciones con una frase por línea",
+    ),
+    ("Normalize preview loudness", "Normalizar el volumen de las voces"),
+    (
+        "Play every voice at the same loudness, for previews and transcript playback",
+        "Reproduce todas las voces al mismo volumen, en las muestras y al leer la transcripción",
+    ),
+    ("Right-click to pin to the top", "Clic derecho para fijar arriba"),
+    ("Right-click to unpin", "Clic derecho para desfijar"),
+    (
+        "Show only the record button, transcript, Copy and Save",
+        "Muestra solo el botón de grabar, la transcripción, Copiar y Guardar",
+    ),
+    (
+        "Names, terms or style instructions for the transcription model",
+        "Nombres, términos o instrucciones de estilo para el modelo de transcripción",
+    ),
+    ("Prompt per language", "Indicación por idioma"),
+    ("Add language prompt", "Añadir indicación por idioma"),
+    (
+        "Closing discards the current transcript.",
+        "Al cerrar se descarta la transcripción actual.",
+    ),
+    (
+        "Finishing transcription before closing...",
+        "Terminando la transcripción antes de cerrar...",
+    ),
+    ("Format text file…", "Formatear archivo de texto…"),
+    (
+        "Clean up a .txt file with the text processing settings, without any API call",
+        "Limpia un archivo .txt con los ajustes de procesamiento de texto, sin llamar a la API",
+    ),
+    ("Choose Text File", "Elegir archivo de texto"),
+    ("Save Formatted Text", "Guardar texto formateado"),
+    ("Formatted text saved to {path}", "Texto formateado guardado en {path}"),
+    (
+        "Choose a Whisper model file in Settings to use the local engine",
+        "Elige un archivo de modelo de Whisper en Ajustes para usar el motor local",
+    ),
+    ("Start recording", "Empezar a grabar"),
+    ("Stop recording", "Detener la grabación"),
+    ("Show", "Mostrar"),
+    ("Quit", "Salir"),
+    (
+        "↑/↓ choose a voice in the highlighted row, Space plays it",
+        "↑/↓ elige una voz en la fila resaltada, Espacio la reproduce",
+    ),
+    ("Save speech…", "Guardar voz…"),
+    (
+        "Save the generated speech as a WAV file",
+        "Guarda la voz generada como archivo WAV",
+    ),
+    ("Save Speech", "Guardar voz"),
+    ("Speech saved to {path}", "Voz guardada en {path}"),
+];
+
+const DUTCH: &[(&str, &str)] = &[
+    ("Press to start listening", "Druk om te beginnen met luisteren"),
+    ("Start Listening", "Begin met luisteren"),
+    ("Stop Listening", "Stop met luisteren"),
+    ("Listening live...", "Live aan het luisteren..."),
+    ("Translating live to {language}", "Live vertalen naar {language}"),
+    ("Stopped", "Gestopt"),
+    ("Settings", "Instellingen"),
+    ("Profiles…", "Profielen…"),
+    ("Profiles", "Profielen"),
+    ("Profile:", "Profiel:"),
+    ("Switched to profile \"{name}\"", "Overgeschakeld naar profiel \"{name}\""),
+    ("Active profile: {name}", "Actief profiel: {name}"),
+    ("No profile selected", "Geen profiel geselecteerd"),
+    ("Name", "Naam"),
+    ("Create from current settings", "Maken van huidige instellingen"),
+    ("Rename active", "Actieve hernoemen"),
+    ("Delete active", "Actieve verwijderen"),
+    ("Log ({count})", "Logboek ({count})"),
+    ("Copy log", "Logboek kopiëren"),
+    ("Copied error log", "Foutenlogboek gekopieerd"),
+    ("Clear", "Wissen"),
+    ("Save", "Opslaan"),
+    ("Copy", "Kopiëren"),
+    ("Play", "Afspelen"),
+    ("Stop", "Stoppen"),
+    ("Loop", "Herhalen"),
+    ("Cancel", "Annuleren"),
+    ("Discard", "Weggooien"),
+    ("Transcribe", "Transcriberen"),
+    ("Transcribe from URL", "Transcriberen vanaf URL"),
+    ("Transcribe from URL…", "Transcriberen vanaf URL…"),
+    (
+        "Link to an audio file (WAV, MP3, FLAC or OGG)",
+        "Link naar een audiobestand (WAV, MP3, FLAC of OGG)",
+    ),
+    (
+        "Transcribing a URL replaces the current transcript.",
+        "Een URL transcriberen vervangt het huidige transcript.",
+    ),
+    (
+        "Downloading and transcribing audio...",
+        "Audio downloaden en transcriberen...",
+    ),
+    ("Transcribed audio from URL", "Audio van URL getranscribeerd"),
+    ("Transcription failed", "Transcriptie mislukt"),
+    ("Unsaved transcript", "Niet-opgeslagen transcriptie"),
+    (
+        "Starting a new recording clears the current transcript.",
+        "Een nieuwe opname wist de huidige transcriptie.",
+    ),
+    (
+        "Clearing removes the current transcript.",
+        "Wissen verwijdert de huidige transcriptie.",
+    ),
+    ("No speech detected", "Geen spraak gedetecteerd"),
+    (
+        "This clip may not contain speech — transcribe anyway?",
+        "Deze opname bevat mogelijk geen spraak — toch transcriberen?",
+    ),
+    (
+        "Format the microphone delivered, before conversion for upload",
+        "Formaat van de microfoon, vóór conversie voor verzending",
+    ),
+    ("Retry recording", "Opname opnieuw proberen"),
+    ("Rate limited, retrying in {seconds}s", "Limiet bereikt, nieuwe poging over {seconds} s"),
+    ("Est. cost this session: {cost}", "Geschatte kosten deze sessie: {cost}"),
+    ("Requests left: {remaining} of {limit}", "Resterende verzoeken: {remaining} van {limit}"),
+    ("Tokens left: {remaining} of {limit}", "Resterende tokens: {remaining} van {limit}"),
+    ("Edit", "Bewerken"),
+    ("Edit the transcript", "Het transcript bewerken"),
+    ("Done", "Klaar"),
+    ("Show paragraphs with copy buttons", "Alinea's met kopieerknoppen tonen"),
+    ("Nothing transcribed yet", "Nog niets getranscribeerd"),
+    ("Copy paragraph", "Alinea kopiëren"),
+    ("Copied paragraph", "Alinea gekopieerd"),
+    ("Bilingual interleaved translation", "Tweetalige vertaling, om en om"),
+    ("Recording too short", "Opname te kort"),
+    ("Shift+click copies as a single line", "Shift+klik kopieert als één regel"),
+    ("Copy as single line", "Kopiëren als één regel"),
+    ("Write numbers as digits", "Getallen in cijfers schrijven"),
+    ("Regenerate speech", "Spraak opnieuw maken"),
+    ("Transcript + Translation", "Transcript + vertaling"),
+    ("Save Transcript + Translation", "Transcript + vertaling opslaan"),
+    (
+        "Save both panes to one .txt, .md or .json file",
+        "Beide panelen in één .txt-, .md- of .json-bestand opslaan",
+    ),
+    ("Nothing to save yet", "Nog niets om op te slaan"),
+    ("Text", "Tekst"),
+    ("Languages", "Talen"),
+    ("Voices", "Stemmen"),
+    ("Audio input", "Audio-invoer"),
+    ("Text processing", "Tekstverwerking"),
+    ("Engine and API", "Engine en API"),
+    (
+        "Synthesize again with the current voice, ignoring the cached speech",
+        "Opnieuw synthetiseren met de huidige stem, zonder de opgeslagen spraak",
+    ),
+    (
+        "Translation hidden; turn translation on to see it again",
+        "Vertaling verborgen; zet vertalen aan om haar weer te zien",
+    ),
+    (
+        "English only: \"three hundred and twelve\" becomes 312; \"one of the\" is kept",
+        "Alleen Engels: \"three hundred and twelve\" wordt 312; \"one of the\" blijft staan",
+    ),
+    (
+        "Translate now alternates each source sentence with its translation",
+        "Nu vertalen wisselt elke bronzin af met de vertaling ervan",
+    ),
+    (
+        "Rough estimate from the price table in settings.json",
+        "Ruwe schatting op basis van de prijstabel in settings.json",
+    ),
+    ("Live session error", "Fout in de live-sessie"),
+    ("Microphone error: {error}", "Microfoonfout: {error}"),
+    ("Recording stopped: microphone lost", "Opname gestopt: microfoon verdwenen"),
+    ("Nothing to {verb} — {pane} is empty", "Niets om te {verb} — {pane} is leeg"),
+    ("copy", "kopiëren"),
+    ("save", "opslaan"),
+    ("play", "af te spelen"),
+    ("transcript", "transcriptie"),
+    ("translation", "vertaling"),
+    ("Generating speech...", "Spraak genereren..."),
+    ("Playing transcript ({voice})", "Transcriptie afspelen ({voice})"),
+    ("Previewing {voice}", "Voorbeeld van {voice}"),
+    ("Synthesizing {segment}/{total}", "Synthetiseren {segment}/{total}"),
+    ("Input: {rate} Hz · {channels} ch", "Invoer: {rate} Hz · {channels} kan."),
+    ("Speech canceled", "Spraak geannuleerd"),
+    ("Speech synthesis failed", "Spraaksynthese mislukt"),
+    ("Translating to {language}...", "Vertalen naar {language}..."),
+    ("Translated to {language}", "Vertaald naar {language}"),
+    ("Translation failed", "Vertaling mislukt"),
+    ("Copied to clipboard", "Gekopieerd naar klembord"),
+    ("Copied {pane}", "{pane} gekopieerd"),
+    ("Transcript saved to {path}", "Transcriptie opgeslagen in {path}"),
+    ("Save Transcript", "Transcriptie opslaan"),
+    ("cached · {voice}", "in cache · {voice}"),
+    ("Play reuses the speech already generated", "Afspelen hergebruikt de al gegenereerde spraak"),
+    (
+        "The voice or text changed; Play will generate new speech with {voice}",
+        "Stem of tekst is gewijzigd; Afspelen genereert nieuwe spraak met {voice}",
+    ),
+    ("Mark the start of a repeat region", "Begin van het te herhalen stuk markeren"),
+    ("Mark the end and repeat from A", "Einde markeren en herhalen vanaf A"),
+    ("Live translation on", "Live vertalen aan"),
+    ("Live translation off", "Live vertalen uit"),
+    ("Clear the transcript and generated speech", "Transcriptie en gegenereerde spraak wissen"),
+    ("Replay the clip until stopped", "Opname herhalen tot je stopt"),
+    ("Stop generating speech", "Stoppen met spraak genereren"),
+    ("Female", "Vrouw"),
+    ("Male", "Man"),
+    ("Origin language", "Brontaal"),
+    ("Translate Live", "Live vertalen"),
+    ("Toggle with Ctrl+T", "Wisselen met Ctrl+T"),
+    ("Target language", "Doeltaal"),
+    ("Translate now", "Nu vertalen"),
+    (
+        "Re-translate the edited source transcript (Ctrl+Enter)",
+        "De bewerkte brontranscriptie opnieuw vertalen (Ctrl+Enter)",
+    ),
+    ("Source transcript", "Brontranscriptie"),
+    ("Translated transcript", "Vertaalde transcriptie"),
+    (
+        "Save, Copy and Play act on the selected pane",
+        "Opslaan, Kopiëren en Afspelen werken op het geselecteerde paneel",
+    ),
+    ("Source speech will appear here...", "Hier verschijnt de gesproken tekst..."),
+    ("Live translation will appear here...", "Hier verschijnt de live vertaling..."),
+    ("Transcribed text will appear here...", "Hier verschijnt de getranscribeerde tekst..."),
+    ("Interface language", "Taal van de interface"),
+    ("System", "Systeem"),
+    ("Default language", "Standaardtaal"),
+    ("Translate by default", "Standaard vertalen"),
+    ("Default target language", "Standaard doeltaal"),
+    ("Female voice", "Vrouwenstem"),
+    ("Male voice", "Mannenstem"),
+    (
+        "Use ↑/↓ to change and Space to preview",
+        "Gebruik ↑/↓ om te wisselen en Spatie om te beluisteren",
+    ),
+    ("Preview text", "Voorbeeldtekst"),
+    ("Voice per language", "Stem per taal"),
+    ("Remove", "Verwijderen"),
+    ("Add language voice", "Stem per taal toevoegen"),
+    ("Engine", "Engine"),
+    ("Apply text formatting", "Tekstopmaak toepassen"),
+    (
+        "Tidy whitespace and drop looped sentences; turn off to keep the model's exact text",
+        "Ruimt witruimte op en verwijdert herhaalde zinnen; zet uit om de exacte tekst van het model te houden",
+    ),
+    ("Reduce low-frequency noise", "Laagfrequente ruis verminderen"),
+    (
+        "Filter out rumble below 80 Hz (air conditioning, desk bumps) before transcription",
+        "Filtert gerommel onder 80 Hz (airco, stoten tegen het bureau) vóór het transcriberen",
+    ),
+    ("Model file", "Modelbestand"),
+    ("Browse…", "Bladeren…"),
+    ("Choose Whisper Model", "Whisper-model kiezen"),
+    ("Recording cache folder", "Cachemap voor opnamen"),
+    (
+        "Continuous dictation writes the recording here instead of keeping it in memory; leave empty to use memory",
+        "Doorlopend dicteren schrijft de opname hierheen in plaats van in het geheugen; laat leeg om het geheugen te gebruiken",
+    ),
+    ("Choose Recording Cache Folder", "Cachemap voor opnamen kiezen"),
+    (
+        "No network: recording for local transcription...",
+        "Geen netwerk: opnemen voor lokale transcriptie...",
+    ),
+    (
+        "Also used by the OpenAI engine when there is no network connection",
+        "Ook gebruikt door de OpenAI-engine als er geen netwerkverbinding is",
+    ),
+    ("Saved WAV depth", "Bitdiepte van opgeslagen WAV"),
+    (
+        "Bit depth of the WAV files written by Save speech… and --tts-out",
+        "Bitdiepte van de WAV-bestanden die Spraak opslaan… en --tts-out schrijven",
+    ),
+    ("16-bit", "16 bit"),
+    ("24-bit", "24 bit"),
+    ("32-bit float", "32 bit float"),
+    ("Temperature", "Temperatuur"),
+    ("Label speakers", "Sprekers labelen"),
+    ("Chunk length (s)", "Fragmentlengte (s)"),
+    ("Chunk overlap (s)", "Fragmentoverlap (s)"),
+    (
+        "Long recordings are transcribed in pieces of this length",
+        "Lange opnames worden in stukken van deze lengte getranscribeerd",
+    ),
+    (
+        "Audio repeated between pieces so words at the cut are not lost",
+        "Audio die tussen stukken herhaald wordt zodat woorden bij de snede niet verloren gaan",
+    ),
+    ("Average confidence: {percent}%", "Gemiddelde betrouwbaarheid: {percent}%"),
+    (
+        "Underlined passages are worth proofreading",
+        "Onderstreepte passages zijn het nalezen waard",
+    ),
+    (
+        "Prefixes each speaker turn with \"Speaker 1:\" or \"Speaker 2:\"; needs a tinydiarize (tdrz) model",
+        "Zet \"Speaker 1:\" of \"Speaker 2:\" voor elke spreekbeurt; vereist een tinydiarize-model (tdrz)",
+    ),
+    (
+        "0 is deterministic and least likely to invent text on noisy audio",
+        "0 is deterministisch en verzint het minst bij audio met ruis",
+    ),
+    ("Request timeout (s)", "Time-out verzoek (s)"),
+    ("Connect timeout (s)", "Time-out verbinding (s)"),
+    ("Proxy", "Proxy"),
+    ("Export settings…", "Instellingen exporteren…"),
+    ("Import settings…", "Instellingen importeren…"),
+    ("Export Settings", "Instellingen exporteren"),
+    ("Import Settings", "Instellingen importeren"),
+    ("Settings exported to {path}", "Instellingen geëxporteerd naar {path}"),
+    ("Settings imported from {path}", "Instellingen geïmporteerd uit {path}"),
+    ("Export failed: {error}", "Exporteren mislukt: {error}"),
+    ("Import failed: {error}", "Importeren mislukt: {error}"),
+    ("Connected to live session", "Verbonden met live-sessie"),
+    ("Audio capture stopped", "Audio-opname gestopt"),
+    ("Recording for local transcription...", "Opnemen voor lokale transcriptie..."),
+    ("Transcribing locally...", "Lokaal transcriberen..."),
+    ("Transcribing locally ({progress})...", "Lokaal transcriberen ({progress})..."),
+    ("Recording may not contain speech", "Opname bevat mogelijk geen spraak"),
+    ("Disconnected", "Verbinding verbroken"),
+    ("Live state: {state}", "Live-status: {state}"),
+    ("OPENAI_API_KEY not configured", "OPENAI_API_KEY is niet ingesteld"),
+    ("OpenAI client unavailable", "OpenAI-client niet beschikbaar"),
+    ("Realtime runtime unavailable", "Realtime-runtime niet beschikbaar"),
+    ("Async runtime unavailable", "Async-runtime niet beschikbaar"),
+    ("Audio output unavailable", "Geen audio-uitvoer beschikbaar"),
+    ("Failed saving profiles: {error}", "Profielen opslaan mislukt: {error}"),
+    ("Clipboard error: {error}", "Klembordfout: {error}"),
+    ("Failed to save file: {error}", "Bestand opslaan mislukt: {error}"),
+    ("Failed to read file: {error}", "Bestand lezen mislukt: {error}"),
+    ("Transcription prompt", "Transcriptie-instructie"),
+    ("Compact", "Compact"),
+    ("Restored previous session", "Vorige sessie hersteld"),
+    ("Recover unsaved transcript", "Niet-opgeslagen transcriptie herstellen"),
+    (
+        "The last session ended with transcript edits that were never saved.",
+        "De vorige sessie eindigde met wijzigingen in de transcriptie die nooit zijn opgeslagen.",
+    ),
+    ("Restore", "Herstellen"),
+    ("Expected languages", "Verwachte talen"),
+    (
+        "Choose two or more for recordings that switch languages",
+        "Kies er twee of meer voor opnames die van taal wisselen",
+    ),
+    (
+        "Detected automatically among the expected languages",
+        "Wordt automatisch herkend tussen de verwachte talen",
+    ),
+    ("Open Privacy Settings", "Privacyinstellingen openen"),
+    (
+        "dict-ai-te may not use the microphone. Allow it in System Settings > Privacy & Security > Microphone, then try again.",
+        "dict-ai-te mag de microfoon niet gebruiken. Sta het toe via Systeeminstellingen > Privacy en beveiliging > Microfoon en probeer het opnieuw.",
+    ),
+    (
+        "The system refused access to the microphone. Check that this user may record audio, then try again.",
+        "Het systeem weigerde toegang tot de microfoon. Controleer of deze gebruiker audio mag opnemen en probeer het opnieuw.",
+    ),
+    ("Dismiss", "Sluiten"),
+    (
+        "No installed font shows {script} text. Put {font} from the Noto fonts in assets/fonts/, then choose Reload fonts in Settings.",
+        "Geen geïnstalleerd lettertype toont {script} tekst. Zet {font} uit de Noto-lettertypen in assets/fonts/ en kies daarna Lettertypen herladen in Instellingen.",
+    ),
+    ("Reload fonts", "Lettertypen herladen"),
+    (
+        "Loads fonts added to assets/fonts/ or the system since dict-ai-te started",
+        "Laadt lettertypen die sinds de start van dict-ai-te aan assets/fonts/ of het systeem zijn toegevoegd",
+    ),
+    ("Fonts reloaded", "Lettertypen herladen"),
+    ("Save Session as JSON", "Sessie opslaan als JSON"),
+    ("Save session as JSON…", "Sessie opslaan als JSON…"),
+    ("Retry", "Opnieuw proberen"),
+    (
+        "Download and transcribe the same URL again",
+        "Dezelfde URL opnieuw downloaden en transcriberen",
+    ),
+    (
+        "Translate the source transcript again",
+        "De brontranscriptie opnieuw vertalen",
+    ),
+    ("Show raw", "Ruwe tekst tonen"),
+    ("Compare with the text before formatting", "Vergelijken met de tekst vóór opmaak"),
+    ("Raw model output", "Ruwe modeluitvoer"),
+    ("Unformatted text from the transcription service", "Onopgemaakte tekst van de transcriptiedienst"),
+    ("Continuous dictation", "Doorlopend dicteren"),
+    ("Transcribe in slices cut at pauses instead of a live session", "In stukken transcriberen die bij pauzes worden geknipt in plaats van een livesessie"),
+    ("Continuous dictation needs the OpenAI engine", "Doorlopend dicteren vereist de OpenAI-engine"),
+    ("Stop listening to switch modes", "Stop met luisteren om van modus te wisselen"),
+    ("Dictating...", "Dicteren..."),
+    ("Transcribing the last slice...", "Laatste stuk wordt getranscribeerd..."),
+    ("Transcribing slice {number} failed: {error}", "Transcriptie van stuk {number} mislukt: {error}"),
+    (
+        "Right-click to save the whole session as JSON",
+        "Rechtsklik om de hele sessie als JSON op te slaan",
+    ),
+    ("Arabic", "Arabische"),
+    ("Hebrew", "Hebreeuwse"),
+    ("Devanagari", "Devanagari"),
+    ("Thai", "Thaise"),
+    ("Chinese, Japanese or Korean", "Chinese, Japanse of Koreaanse"),
+    ("Font download URL", "Download-URL voor lettertypen"),
+    (
+        "Folder with Noto font files and a SHA256SUMS list; enables Download fonts",
+        "Map met Noto-lettertypebestanden en een SHA256SUMS-lijst; schakelt Lettertypen downloaden in",
+    ),
+    ("Download fonts", "Lettertypen downloaden"),
+    ("Saves the fonts to {path}", "Slaat de lettertypen op in {path}"),
+    ("Downloading fonts...", "Lettertypen downloaden..."),
+    ("Fonts installed", "Lettertypen geïnstalleerd"),
+    ("Font download failed: {error}", "Downloaden van lettertypen mislukt: {error}"),
+    ("Font download failed", "Downloaden van lettertypen mislukt"),
+    ("Paragraphs", "Alinea's"),
+    ("Blank lines only", "Alleen lege regels"),
+    ("Group sentences", "Zinnen groeperen"),
+    (
+        "Group sentences starts a new paragraph every few sentences, for transcripts with a sentence on every line",
+        "Zinnen groeperen begint om de paar zinnen een nieuwe alinea, voor transcripties met een zin per regel",
+    ),
+    ("Normalize preview loudness", "Stemvolume gelijktrekken"),
+    (
+        "Play every voice at the same loudness, for previews and transcript playback",
+        "Speel elke stem even luid af, bij voorbeelden en bij het voorlezen van de transcriptie",
+    ),
+    ("Right-click to pin to the top", "Rechtsklik om bovenaan vast te zetten"),
+    ("Right-click to unpin", "Rechtsklik om los te maken"),
+    (
+        "Show only the record button, transcript, Copy and Save",
+        "Toont alleen de opnameknop, het transcript, Kopiëren en Opslaan",
+    ),
+    (
+        "Names, terms or style instructions for the transcription model",
+        "Namen, termen of stijlinstructies voor het transcriptiemodel",
+    ),
+    ("Prompt per language", "Instructie per taal"),
+    ("Add language prompt", "Taalinstructie toevoegen"),
+    (
+        "Closing discards the current transcript.",
+        "Bij het sluiten gaat het huidige transcript verloren.",
+    ),
+    (
+        "Finishing transcription before closing...",
+        "Transcriptie afronden voor het sluiten...",
+    ),
+    ("Format text file…", "Tekstbestand opmaken…"),
+    (
+        "Clean up a .txt file with the text processing settings, without any API call",
+        "Schoont een .txt-bestand op met de tekstverwerkingsinstellingen, zonder API-aanroep",
+    ),
+    ("Choose Text File", "Tekstbestand kiezen"),
+    ("Save Formatted Text", "Opgemaakte tekst opslaan"),
+    ("Formatted text saved to {path}", "Opgemaakte tekst opgeslagen in {path}"),
+    (
+        "Choose a Whisper model file in Settings to use the local engine",
+        "Kies een Whisper-modelbestand in Instellingen om de lokale engine te gebruiken",
+    ),
+    ("Start recording", "Opname starten"),
+    ("Stop recording", "Opname stoppen"),
+    ("Show", "Tonen"),
+    ("Quit", "Afsluiten"),
+    (
+        "↑/↓ choose a voice in the highlighted row, Space plays it",
+        "↑/↓ kies een stem in de gemarkeerde rij, Spatie speelt hem af",
+    ),
+    ("Save speech…", "Spraak opslaan…"),
+    (
+        "Save the generated speech as a WAV file",
+        "Slaat de gegenereerde spraak op als WAV-bestand",
+    ),
+    ("Save Speech", "Spraak opslaan"),
+    ("Speech saved to {path}", "Spraak opgeslagen in {path}"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_language_from_locale() {
+        assert_eq!(locale_language("nl_NL.UTF-8").as_deref(), Some("nl"));
+        assert_eq!(locale_language("es").as_deref(), Some("es"));
+        assert_eq!(locale_language("C.UTF-8"), None);
+        assert_eq!(locale_language(""), None);
+    }
+
+    #[test]
+    fn falls_back_to_the_key_and_fills_placeholders() {
+        assert_eq!(lookup("es", "Settings"), "Ajustes");
+        assert_eq!(lookup("es", "Not in any table"), "Not in any table");
+        assert_eq!(lookup("en", "Settings"), "Settings");
+        let text = t_with("Translated to {language}", &[("language", &"English")]);
+        assert!(text.ends_with("English"), "{text}");
+    }
+
+    #[test]
+    fn interface_text_goes_through_the_tables() {
+        let sources = [
+            include_str!("app.rs"),
+            include_str!("fonts.rs"),
+            include_str!("tray.rs"),
+        ];
+        let keyed = regex::Regex::new(r#"\bt(?:_with)?\(\s*"((?:[^"\\]|\\.)*)""#).unwrap();
+        let bare =
+            regex::Regex::new(r#"(?:RichText::new|\.label|\.button|on_hover_text)\(\s*"([^"]*)""#)
+                .unwrap();
+        // Names and symbols that read the same in every language.
+        let untranslated = ["dict-ai-te", "✕ A-B"];
+        for source in sources {
+            for found in keyed.captures_iter(source) {
+                let key = found[1].replace("\\\"", "\"");
+                assert!(
+                    SPANISH.iter().any(|(known, _)| *known == key),
+                    "no translation for {key:?}"
+                );
+            }
+            for found in bare.captures_iter(source) {
+                assert!(
+                    untranslated.contains(&&found[1]),
+                    "{:?} is shown without t()",
+                    &found[1]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tables_cover_the_same_keys() {
+        let keys = |table: &[(&'static str, &str)]| {
+            let mut keys: Vec<_> = table.iter().map(|(key, _)| *key).collect();
+            keys.sort_unstable();
+            keys
+        };
+        assert_eq!(keys(SPANISH), keys(DUTCH));
+        for (key, text) in SPANISH.iter().chain(DUTCH) {
+            for placeholder in key
+                .split('{')
+                .skip(1)
+                .filter_map(|rest| rest.split_once('}'))
+            {
+                let placeholder = format!("{{{}}}", placeholder.0);
+                assert!(text.contains(&placeholder), "{text} lacks {placeholder}");
+            }
+        }
+    }
+}
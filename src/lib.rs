@@ -0,0 +1,20 @@
+//! Core of dict-ai-te without the egui front-end: OpenAI clients for realtime
+//! transcription, translation and speech, audio capture and playback, text
+//! helpers and the shared settings store.
+
+pub mod audio;
+pub mod constants;
+pub mod error;
+pub mod font_download;
+pub mod openai;
+pub mod profiles;
+pub mod realtime;
+pub mod scratch;
+pub mod session_export;
+pub mod settings;
+pub mod text_utils;
+
+pub use audio::{AudioClip, AudioPlayer, LiveCapture, Recorder};
+pub use error::AppError;
+pub use openai::OpenAiClient;
+pub use settings::Settings;
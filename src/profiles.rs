@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{config_dir, fill_defaults, write_json_atomically, Settings};
+
+const PROFILES_FILENAME: &str = "profiles.json";
+
+/// Named settings snapshots. The active profile's values are also written to
+/// the regular settings file so other front-ends keep seeing them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStore {
+    pub active: Option<String>,
+    pub profiles: BTreeMap<String, Settings>,
+}
+
+impl ProfileStore {
+    pub fn load() -> Self {
+        match Self::load_from_path(&default_profiles_path()) {
+            Ok(store) => store,
+            Err(err) => {
+                log::warn!("Failed loading profiles: {err:#}");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(raw) => {
+                let mut store: ProfileStore = serde_json::from_str(&raw)
+                    .with_context(|| format!("Invalid JSON in {}", path.display()))?;
+                store.profiles = store
+                    .profiles
+                    .into_iter()
+                    .map(|(name, settings)| (name, fill_defaults(settings)))
+                    .collect();
+                if store
+                    .active
+                    .as_ref()
+                    .is_some_and(|name| !store.profiles.contains_key(name))
+                {
+                    store.active = None;
+                }
+                Ok(store)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("Failed reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to_path(&default_profiles_path())
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        write_json_atomically(self, path)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    pub fn create(&mut self, name: &str, settings: Settings) -> Result<()> {
+        let name = validate_name(name)?;
+        if self.profiles.contains_key(&name) {
+            bail!("A profile named \"{name}\" already exists");
+        }
+        self.profiles.insert(name.clone(), settings);
+        self.active = Some(name);
+        Ok(())
+    }
+
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<()> {
+        let new = validate_name(new)?;
+        if old == new {
+            return Ok(());
+        }
+        if self.profiles.contains_key(&new) {
+            bail!("A profile named \"{new}\" already exists");
+        }
+        let settings = self
+            .profiles
+            .remove(old)
+            .ok_or_else(|| anyhow!("Unknown profile \"{old}\""))?;
+        self.profiles.insert(new.clone(), settings);
+        if self.active.as_deref() == Some(old) {
+            self.active = Some(new);
+        }
+        Ok(())
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<()> {
+        self.profiles
+            .remove(name)
+            .ok_or_else(|| anyhow!("Unknown profile \"{name}\""))?;
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+        Ok(())
+    }
+
+    pub fn select(&mut self, name: &str) -> Result<Settings> {
+        let settings = self
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("Unknown profile \"{name}\""))?;
+        self.active = Some(name.to_string());
+        Ok(settings)
+    }
+
+    /// Stores edited settings back into the active profile, if any.
+    pub fn update_active(&mut self, settings: &Settings) {
+        if let Some(name) = &self.active {
+            self.profiles.insert(name.clone(), settings.clone());
+        }
+    }
+}
+
+pub fn default_profiles_path() -> PathBuf {
+    config_dir().join(PROFILES_FILENAME)
+}
+
+fn validate_name(name: &str) -> Result<String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        bail!("Profile name cannot be empty");
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_renames_and_deletes_profiles() {
+        let mut store = ProfileStore::default();
+        store.create("Work", Settings::default()).unwrap();
+        assert!(store.create(" Work ", Settings::default()).is_err());
+        assert!(store.create("  ", Settings::default()).is_err());
+        assert_eq!(store.active.as_deref(), Some("Work"));
+
+        store.rename("Work", "Office").unwrap();
+        assert_eq!(store.active.as_deref(), Some("Office"));
+        assert_eq!(store.names().collect::<Vec<_>>(), vec!["Office"]);
+
+        store.delete("Office").unwrap();
+        assert!(store.active.is_none());
+        assert!(store.delete("Office").is_err());
+    }
+
+    #[test]
+    fn select_and_update_active_round_trip() {
+        let mut store = ProfileStore::default();
+        let study = Settings {
+            default_language: Some("ja".into()),
+            translate_by_default: true,
+            ..Settings::default()
+        };
+        store.create("Study", study).unwrap();
+        store.create("Work", Settings::default()).unwrap();
+
+        let selected = store.select("Study").unwrap();
+        assert_eq!(selected.default_language.as_deref(), Some("ja"));
+
+        let mut edited = selected.clone();
+        edited.male_voice = "echo".into();
+        store.update_active(&edited);
+        assert_eq!(store.profiles["Study"].male_voice, "echo");
+        assert_eq!(store.profiles["Work"].male_voice, "onyx");
+    }
+
+    #[test]
+    fn persists_active_profile() {
+        let dir = std::env::temp_dir().join(format!("dictaite-{}-profiles", std::process::id()));
+        let path = dir.join(PROFILES_FILENAME);
+        let mut store = ProfileStore::default();
+        store.create("Work", Settings::default()).unwrap();
+        store.save_to_path(&path).unwrap();
+
+        let loaded = ProfileStore::load_from_path(&path).unwrap();
+        assert_eq!(loaded.active.as_deref(), Some("Work"));
+        assert!(loaded.profiles.contains_key("Work"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
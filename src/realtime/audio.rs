@@ -1,6 +1,60 @@
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
 
 pub const TARGET_SAMPLE_RATE: u32 = 24_000;
+/// Cutoff used by the "reduce low-frequency noise" option; below the lowest
+/// voice fundamentals but above most hum and handling rumble.
+pub const RUMBLE_CUTOFF_HZ: f32 = 80.0;
+
+/// Second-order Butterworth high-pass filter for one channel. The state is
+/// kept between calls so a stream can be filtered chunk by chunk.
+#[derive(Debug, Clone)]
+pub struct HighPass {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl HighPass {
+    pub fn new(cutoff_hz: f32, sample_rate: u32) -> Self {
+        let nyquist = sample_rate as f32 / 2.0;
+        let cutoff = cutoff_hz.clamp(1.0, nyquist * 0.9);
+        let omega = std::f32::consts::TAU * cutoff / sample_rate as f32;
+        let alpha = omega.sin() / std::f32::consts::SQRT_2;
+        let cos = omega.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let x = *sample;
+            let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+                - self.a1 * self.y1
+                - self.a2 * self.y2;
+            self.x2 = self.x1;
+            self.x1 = x;
+            self.y2 = self.y1;
+            self.y1 = y;
+            *sample = y;
+        }
+    }
+}
 
 pub fn downmix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
     if channels <= 1 {
@@ -57,6 +111,37 @@ pub fn base64_pcm16(pcm: &[u8]) -> String {
     BASE64_STANDARD.encode(pcm)
 }
 
+pub fn decode_base64_pcm16(chunk: &str) -> Vec<f32> {
+    let Ok(pcm) = BASE64_STANDARD.decode(chunk) else {
+        return Vec::new();
+    };
+    pcm.chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+        .collect()
+}
+
+/// Wraps raw little-endian PCM16 mono bytes in a WAV container without
+/// touching the samples, so the file matches what was uploaded byte for byte.
+pub fn pcm16_wav_bytes(pcm: &[u8], sample_rate: u32) -> Result<Vec<u8>, hound::Error> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut cursor, spec)?;
+        let mut samples = writer.get_i16_writer(pcm.len() as u32 / 2);
+        for pair in pcm.chunks_exact(2) {
+            samples.write_sample(i16::from_le_bytes([pair[0], pair[1]]));
+        }
+        samples.flush()?;
+        writer.finalize()?;
+    }
+    Ok(cursor.into_inner())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +155,24 @@ mod tests {
         assert_eq!(chunk_pcm16(&pcm, TARGET_SAMPLE_RATE, 20).len(), 1);
         assert!(!base64_pcm16(&pcm).is_empty());
     }
+
+    #[test]
+    fn decodes_base64_pcm_back_to_samples() {
+        let pcm = pcm16_le(&[0.0, 0.5, -1.0]);
+        let decoded = decode_base64_pcm16(&base64_pcm16(&pcm));
+        assert_eq!(decoded.len(), 3);
+        assert!((decoded[1] - 0.5).abs() < 1e-3);
+        assert_eq!(decoded[2], -1.0);
+        assert!(decode_base64_pcm16("not base64!").is_empty());
+    }
+
+    #[test]
+    fn wraps_pcm_in_wav_without_changing_samples() {
+        let pcm = pcm16_le(&[0.0, 0.25, -0.75, 1.0]);
+        let wav = pcm16_wav_bytes(&pcm, TARGET_SAMPLE_RATE).unwrap();
+        assert!(wav.ends_with(&pcm));
+        let reader = hound::WavReader::new(std::io::Cursor::new(wav)).unwrap();
+        assert_eq!(reader.spec().sample_rate, TARGET_SAMPLE_RATE);
+        assert_eq!(reader.len(), 4);
+    }
 }
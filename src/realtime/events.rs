@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum RealtimeEvent {
     SourceDelta {
         item_id: Option<String>,
@@ -9,6 +9,9 @@ pub enum RealtimeEvent {
     SourceCompleted {
         item_id: Option<String>,
         text: String,
+        /// Mean token probability (0.0–1.0) when the session was asked for
+        /// logprobs; `None` when the backend reported none.
+        confidence: Option<f32>,
     },
     TranslationDelta {
         text: String,
@@ -34,6 +37,21 @@ struct RawEvent {
     transcript: Option<String>,
     text: Option<String>,
     error: Option<serde_json::Value>,
+    logprobs: Option<Vec<RawLogprob>>,
+}
+
+#[derive(Deserialize)]
+struct RawLogprob {
+    logprob: f32,
+}
+
+/// Geometric mean of the token probabilities, i.e. `exp` of the mean logprob.
+fn mean_confidence(logprobs: &[RawLogprob]) -> Option<f32> {
+    if logprobs.is_empty() {
+        return None;
+    }
+    let mean = logprobs.iter().map(|token| token.logprob).sum::<f32>() / logprobs.len() as f32;
+    Some(mean.exp())
 }
 
 pub fn parse_event(value: &serde_json::Value) -> RealtimeEvent {
@@ -50,6 +68,7 @@ pub fn parse_event(value: &serde_json::Value) -> RealtimeEvent {
             RealtimeEvent::SourceCompleted {
                 item_id: raw.item_id,
                 text: raw.transcript.or(raw.text).unwrap_or_default(),
+                confidence: raw.logprobs.as_deref().and_then(mean_confidence),
             }
         }
         Some("session.input_transcript.delta") => RealtimeEvent::SourceDelta {
@@ -129,6 +148,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn averages_transcription_logprobs_into_confidence() {
+        let event = parse_event(&json!({
+            "type": "conversation.item.input_audio_transcription.completed",
+            "item_id": "a",
+            "transcript": "Hi there",
+            "logprobs": [
+                {"token": "Hi", "logprob": 0.0, "bytes": [72, 105]},
+                {"token": " there", "logprob": -0.2, "bytes": []}
+            ]
+        }));
+        let RealtimeEvent::SourceCompleted { confidence, .. } = event else {
+            panic!("expected a completed transcript, got {event:?}");
+        };
+        assert!((confidence.unwrap() - (-0.1f32).exp()).abs() < 1e-6);
+
+        let without = parse_event(&json!({
+            "type": "conversation.item.input_audio_transcription.completed",
+            "transcript": "Hi"
+        }));
+        assert!(matches!(
+            without,
+            RealtimeEvent::SourceCompleted {
+                confidence: None,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn parses_ga_realtime_translation_text_delta() {
         let event = parse_event(&json!({
@@ -0,0 +1,558 @@
+use std::ops::Range;
+use std::path::Path;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::audio::AudioClip;
+use crate::error::AppError;
+use crate::realtime::audio::{decode_base64_pcm16, TARGET_SAMPLE_RATE};
+use crate::realtime::events::RealtimeEvent;
+use crate::text_utils::format_structured_text;
+
+/// Sample rate expected by whisper.cpp models.
+#[cfg(feature = "local-whisper")]
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Long sessions are transcribed in pieces of roughly this length unless
+/// [`LocalTranscriber::with_segments`] says otherwise.
+pub const DEFAULT_SEGMENT_SECS: u64 = 5 * 60;
+/// Shortest segment length accepted; it must leave room for the pause search.
+pub const MIN_SEGMENT_SECS: u64 = 30;
+/// How far back from the nominal segment end to look for a pause to cut at.
+const SILENCE_SEARCH: Duration = Duration::from_secs(15);
+const SILENCE_STEP: Duration = Duration::from_millis(100);
+/// Audio repeated at the start of the next segment so words at the seam are
+/// not clipped; the duplicated text is removed when stitching.
+pub const DEFAULT_SEGMENT_OVERLAP_SECS: u64 = 2;
+const MAX_SEAM_WORDS: usize = 40;
+const NO_LOCAL_WHISPER: &str =
+    "This build has no local Whisper support (rebuild with --features local-whisper)";
+
+/// Offline transcription backed by a whisper.cpp model file. Only available
+/// when the crate is built with the `local-whisper` feature; otherwise
+/// [`LocalTranscriber::new`] reports that.
+pub struct LocalTranscriber {
+    #[cfg(feature = "local-whisper")]
+    model_path: std::path::PathBuf,
+    format_text: bool,
+    temperature: f32,
+    speaker_labels: bool,
+    prompt: Option<String>,
+    segment_length: Duration,
+    segment_overlap: Duration,
+    /// Speaker of the text transcribed last, once a turn has been detected.
+    #[cfg(feature = "local-whisper")]
+    speaker: Option<usize>,
+    #[cfg(feature = "local-whisper")]
+    context: Option<whisper_rs::WhisperContext>,
+}
+
+impl LocalTranscriber {
+    pub fn new(model_path: &Path) -> Result<Self, AppError> {
+        if !cfg!(feature = "local-whisper") {
+            return Err(AppError::Message(NO_LOCAL_WHISPER.to_string()));
+        }
+        if !model_path.is_file() {
+            return Err(AppError::Message(format!(
+                "Local Whisper model not found at {}",
+                model_path.display()
+            )));
+        }
+        Ok(Self {
+            #[cfg(feature = "local-whisper")]
+            model_path: model_path.to_path_buf(),
+            format_text: true,
+            temperature: 0.0,
+            speaker_labels: false,
+            prompt: None,
+            segment_length: Duration::from_secs(DEFAULT_SEGMENT_SECS),
+            segment_overlap: Duration::from_secs(DEFAULT_SEGMENT_OVERLAP_SECS),
+            #[cfg(feature = "local-whisper")]
+            speaker: None,
+            #[cfg(feature = "local-whisper")]
+            context: None,
+        })
+    }
+
+    /// Whether segment text goes through `format_structured_text` (the default).
+    pub fn with_formatting(mut self, format_text: bool) -> Self {
+        self.format_text = format_text;
+        self
+    }
+
+    /// Decoding temperature; 0.0 (the default) leaves whisper.cpp's own
+    /// setting untouched.
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = temperature.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Initial prompt that steers spelling and style, e.g. a glossary.
+    pub fn with_prompt(mut self, prompt: Option<String>) -> Self {
+        // whisper.cpp takes a C string; an embedded NUL would cut it short.
+        self.prompt = prompt.map(|prompt| prompt.replace('\0', ""));
+        self
+    }
+
+    /// Length of the pieces long sessions are cut into and the audio shared
+    /// by neighbouring pieces. The length is at least [`MIN_SEGMENT_SECS`]
+    /// and the overlap stays below half of it.
+    pub fn with_segments(mut self, length: Duration, overlap: Duration) -> Self {
+        self.segment_length = length.max(Duration::from_secs(MIN_SEGMENT_SECS));
+        self.segment_overlap = overlap.min(self.segment_length / 2 - Duration::from_secs(1));
+        self
+    }
+
+    /// Asks whisper.cpp for speaker turns and prefixes each turn with
+    /// "Speaker N:". Only tinydiarize models (`*-tdrz.bin`) report turns;
+    /// with other models the text is unchanged.
+    pub fn with_speaker_labels(mut self, speaker_labels: bool) -> Self {
+        self.speaker_labels = speaker_labels;
+        self
+    }
+
+    /// Transcribes mono 24 kHz samples as produced by the live capture. The
+    /// model is loaded on first use and kept for the following segments.
+    #[cfg(feature = "local-whisper")]
+    pub fn transcribe(
+        &mut self,
+        samples: &[f32],
+        language: Option<&str>,
+    ) -> Result<String, AppError> {
+        use crate::realtime::audio::resample_linear;
+        use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+        if self.context.is_none() {
+            let model_path = self.model_path.to_string_lossy();
+            let context = WhisperContext::new_with_params(
+                &model_path,
+                WhisperContextParameters::default(),
+            )
+            .map_err(|err| AppError::Message(format!("Failed loading Whisper model: {err}")))?;
+            self.context = Some(context);
+        }
+        let context = self.context.as_ref().expect("Whisper model loaded above");
+        let mut state = context
+            .create_state()
+            .map_err(|err| AppError::Message(format!("Failed creating Whisper state: {err}")))?;
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_language(Some(language.unwrap_or("auto")));
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        if self.temperature > 0.0 {
+            params.set_temperature(self.temperature);
+        }
+        params.set_tdrz_enable(self.speaker_labels);
+        if let Some(prompt) = self.prompt.as_deref() {
+            params.set_initial_prompt(prompt);
+        }
+
+        let audio = resample_linear(samples, TARGET_SAMPLE_RATE, WHISPER_SAMPLE_RATE);
+        state
+            .full(params, &audio)
+            .map_err(|err| AppError::Message(format!("Local transcription failed: {err}")))?;
+
+        let segments = state
+            .full_n_segments()
+            .map_err(|err| AppError::Message(err.to_string()))?;
+        let mut texts = Vec::new();
+        for idx in 0..segments {
+            let segment = state
+                .full_get_segment_text_lossy(idx)
+                .map_err(|err| AppError::Message(err.to_string()))?;
+            let turn_ends = self.speaker_labels && state.full_get_segment_speaker_turn_next(idx);
+            texts.push((segment, turn_ends));
+        }
+        Ok(join_segments(&texts, &mut self.speaker))
+    }
+
+    /// Without the feature there is nothing to transcribe with; [`Self::new`]
+    /// already refuses such builds, this covers any other way in.
+    #[cfg(not(feature = "local-whisper"))]
+    pub fn transcribe(
+        &mut self,
+        _samples: &[f32],
+        _language: Option<&str>,
+    ) -> Result<String, AppError> {
+        Err(AppError::Message(NO_LOCAL_WHISPER.to_string()))
+    }
+}
+
+/// Buffers the microphone audio of a session and transcribes it locally once
+/// capture stops. Emits the same events as the realtime sessions so the UI
+/// does not need to know which engine produced the text.
+///
+/// When the recording does not look like speech, a `local.confirm_speech`
+/// state is emitted and transcription waits for `confirm_rx`; `false` or a
+/// dropped sender skips it.
+pub async fn run_local_transcription(
+    transcriber: LocalTranscriber,
+    source_language: Option<String>,
+    audio_rx: mpsc::Receiver<String>,
+    event_tx: mpsc::Sender<RealtimeEvent>,
+    stop_rx: oneshot::Receiver<()>,
+    confirm_rx: oneshot::Receiver<bool>,
+) -> Result<(), AppError> {
+    run_local_session(
+        transcriber,
+        source_language,
+        audio_rx,
+        event_tx,
+        stop_rx,
+        confirm_rx,
+        "local.recording",
+    )
+    .await
+}
+
+/// [`run_local_transcription`] announcing the recording with `start_state`.
+pub(crate) async fn run_local_session(
+    mut transcriber: LocalTranscriber,
+    source_language: Option<String>,
+    mut audio_rx: mpsc::Receiver<String>,
+    event_tx: mpsc::Sender<RealtimeEvent>,
+    mut stop_rx: oneshot::Receiver<()>,
+    confirm_rx: oneshot::Receiver<bool>,
+    start_state: &str,
+) -> Result<(), AppError> {
+    let _ = event_tx
+        .send(RealtimeEvent::SessionState {
+            state: start_state.to_string(),
+        })
+        .await;
+
+    let mut clip = AudioClip::from_samples(Vec::new(), TARGET_SAMPLE_RATE, 1);
+    loop {
+        tokio::select! {
+            _ = &mut stop_rx => {
+                while let Ok(chunk) = audio_rx.try_recv() {
+                    clip.append_samples(&decode_base64_pcm16(&chunk));
+                }
+                break;
+            }
+            chunk = audio_rx.recv() => match chunk {
+                Some(chunk) => clip.append_samples(&decode_base64_pcm16(&chunk)),
+                None => break,
+            },
+        }
+    }
+
+    let proceed = !clip.samples().is_empty()
+        && (clip.looks_like_speech() || {
+            let _ = event_tx
+                .send(RealtimeEvent::SessionState {
+                    state: "local.confirm_speech".to_string(),
+                })
+                .await;
+            confirm_rx.await.unwrap_or(false)
+        });
+    if proceed {
+        let blocking_tx = event_tx.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            transcribe_segments(
+                &mut transcriber,
+                &clip,
+                source_language.as_deref(),
+                &blocking_tx,
+            )
+        })
+        .await
+        .map_err(|err| AppError::Message(format!("Local transcription task failed: {err}")))
+        .and_then(|result| result);
+        if let Err(err) = result {
+            let _ = event_tx
+                .send(RealtimeEvent::Error {
+                    message: err.to_string(),
+                })
+                .await;
+        }
+    }
+
+    let _ = event_tx
+        .send(RealtimeEvent::SessionState {
+            state: "disconnected".to_string(),
+        })
+        .await;
+    Ok(())
+}
+
+fn transcribe_segments(
+    transcriber: &mut LocalTranscriber,
+    clip: &AudioClip,
+    language: Option<&str>,
+    event_tx: &mpsc::Sender<RealtimeEvent>,
+) -> Result<(), AppError> {
+    let ranges = segment_ranges(
+        clip,
+        transcriber.segment_length,
+        transcriber.segment_overlap,
+    );
+    let total = ranges.len();
+    let mut previous = String::new();
+    for (index, range) in ranges.into_iter().enumerate() {
+        let state = if total > 1 {
+            format!("local.transcribing {}/{total}", index + 1)
+        } else {
+            "local.transcribing".to_string()
+        };
+        let _ = event_tx.blocking_send(RealtimeEvent::SessionState { state });
+
+        // Earlier segments were already sent to the UI and stay there; say
+        // where the failure happened so the user knows what is missing.
+        let text = transcriber
+            .transcribe(&clip.samples()[range], language)
+            .map_err(|err| {
+                if previous.is_empty() {
+                    err
+                } else {
+                    AppError::Message(format!(
+                        "Transcription partially failed at segment {} of {total}: {err}",
+                        index + 1
+                    ))
+                }
+            })?;
+        let text = if transcriber.format_text {
+            format_structured_text(&text)
+        } else {
+            text
+        };
+        let text = drop_repeated_lead(&previous, &text);
+        if text.is_empty() {
+            continue;
+        }
+        let _ = event_tx.blocking_send(RealtimeEvent::SourceCompleted {
+            item_id: Some(format!("local-{index}")),
+            text: text.clone(),
+            confidence: None,
+        });
+        previous = text;
+    }
+    Ok(())
+}
+
+/// Joins whisper segments, each paired with whether a speaker turn follows
+/// it. Once a turn has been seen, every turn becomes a "Speaker N:"
+/// paragraph; `speaker` carries the current speaker into the next call so a
+/// long session keeps counting. tinydiarize only marks turns, not voices, so
+/// the labels alternate between two speakers as in an interview.
+#[cfg(any(feature = "local-whisper", test))]
+fn join_segments(segments: &[(String, bool)], speaker: &mut Option<usize>) -> String {
+    if speaker.is_none() && !segments.iter().any(|(_, turn_ends)| *turn_ends) {
+        return segments
+            .iter()
+            .map(|(text, _)| text.trim())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    let current = speaker.get_or_insert(1);
+    let mut paragraphs = Vec::new();
+    let mut turn = Vec::new();
+    for (text, turn_ends) in segments {
+        let text = text.trim();
+        if !text.is_empty() {
+            turn.push(text);
+        }
+        if *turn_ends && !turn.is_empty() {
+            paragraphs.push(format!("Speaker {current}: {}", turn.join(" ")));
+            turn.clear();
+            *current = 3 - *current;
+        }
+    }
+    if !turn.is_empty() {
+        paragraphs.push(format!("Speaker {current}: {}", turn.join(" ")));
+    }
+    paragraphs.join("\n\n")
+}
+
+/// Splits a mono clip into segments of about `length`, ending each one at
+/// the quietest point shortly before the nominal cut and starting the next
+/// one `overlap` earlier.
+fn segment_ranges(clip: &AudioClip, length: Duration, overlap: Duration) -> Vec<Range<usize>> {
+    let total = clip.duration();
+    let to_index = |at: Duration| {
+        ((at.as_secs_f64() * clip.sample_rate as f64) as usize).min(clip.samples().len())
+    };
+
+    let mut ranges = Vec::new();
+    let mut start = Duration::ZERO;
+    loop {
+        let nominal_end = start + length;
+        if nominal_end >= total {
+            ranges.push(to_index(start)..clip.samples().len());
+            break;
+        }
+        let search_from = nominal_end
+            .saturating_sub(SILENCE_SEARCH)
+            .max(start + overlap * 2);
+        let cut = quietest_point(clip, search_from, nominal_end);
+        ranges.push(to_index(start)..to_index(cut));
+        start = cut - overlap;
+    }
+    ranges
+}
+
+fn quietest_point(clip: &AudioClip, from: Duration, to: Duration) -> Duration {
+    let mut best = to;
+    let mut best_level = clip.level_at(to);
+    let mut at = to;
+    while at > from {
+        at = at.saturating_sub(SILENCE_STEP).max(from);
+        let level = clip.level_at(at);
+        if level < best_level {
+            best = at;
+            best_level = level;
+        }
+    }
+    best
+}
+
+/// Removes words at the start of `next` that repeat the end of `previous`,
+/// which happens because consecutive segments share a little audio. A single
+/// shared word is left alone since it is as likely to be a real repetition.
+fn drop_repeated_lead(previous: &str, next: &str) -> String {
+    let previous_words: Vec<String> = previous.split_whitespace().map(normalize_word).collect();
+    let next_words: Vec<&str> = next.split_whitespace().collect();
+    let max = previous_words
+        .len()
+        .min(next_words.len())
+        .min(MAX_SEAM_WORDS);
+    let overlap = (2..=max)
+        .rev()
+        .find(|&len| {
+            previous_words[previous_words.len() - len..]
+                .iter()
+                .zip(&next_words[..len])
+                .all(|(prev, next)| *prev == normalize_word(next))
+        })
+        .unwrap_or(0);
+    // Slice the original rather than re-joining the words so paragraph breaks,
+    // such as those between speaker turns, survive.
+    let start = next
+        .char_indices()
+        .zip(std::iter::once(' ').chain(next.chars()))
+        .filter(|((_, ch), before)| !ch.is_whitespace() && before.is_whitespace())
+        .map(|((idx, _), _)| idx)
+        .nth(overlap);
+    match start {
+        Some(start) => next[start..].to_string(),
+        None => String::new(),
+    }
+}
+
+fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_model_file_is_reported() {
+        let err = LocalTranscriber::new(Path::new("/nonexistent/ggml-base.bin"))
+            .err()
+            .unwrap();
+        let expected = if cfg!(feature = "local-whisper") {
+            "model not found"
+        } else {
+            "no local Whisper support"
+        };
+        assert!(err.to_string().contains(expected));
+    }
+
+    #[test]
+    fn short_clips_are_a_single_segment() {
+        let clip = AudioClip::from_samples(vec![0.1; 1_000], TARGET_SAMPLE_RATE, 1);
+        assert_eq!(
+            segment_ranges(&clip, Duration::from_secs(300), Duration::from_secs(2)),
+            vec![0..1_000]
+        );
+    }
+
+    #[test]
+    fn long_clips_are_cut_at_pauses_with_overlap() {
+        let rate = 1_000;
+        let seconds = 12 * 60;
+        let mut samples = vec![0.5; rate * seconds];
+        // A one-second pause ten seconds before the nominal five-minute cut.
+        let pause = (5 * 60 - 10) * rate;
+        samples[pause..pause + rate].fill(0.0);
+        let clip = AudioClip::from_samples(samples, rate as u32, 1);
+
+        let ranges = segment_ranges(&clip, Duration::from_secs(300), Duration::from_secs(2));
+        assert_eq!(ranges.len(), 3);
+        assert!(ranges[0].end > pause && ranges[0].end < pause + rate);
+        assert_eq!(ranges[1].start, ranges[0].end - 2 * rate);
+        assert_eq!(ranges[2].end, rate * seconds);
+
+        // Shorter pieces with a wider overlap.
+        let ranges = segment_ranges(&clip, Duration::from_secs(120), Duration::from_secs(5));
+        assert_eq!(ranges.len(), 7);
+        assert!(ranges
+            .windows(2)
+            .all(|pair| pair[1].start == pair[0].end - 5 * rate));
+    }
+
+    #[test]
+    fn drops_words_repeated_across_the_seam() {
+        assert_eq!(
+            drop_repeated_lead("We met on Monday. It rained", "it rained all day."),
+            "all day."
+        );
+        assert_eq!(
+            drop_repeated_lead("First part.", "Second part."),
+            "Second part."
+        );
+        assert_eq!(drop_repeated_lead("", "Hello."), "Hello.");
+        assert_eq!(
+            drop_repeated_lead("It rained", "it rained.\n\nSpeaker 2: Yes."),
+            "Speaker 2: Yes."
+        );
+        assert_eq!(
+            drop_repeated_lead("First.", "Speaker 1: Hi.\n\nSpeaker 2: Yes."),
+            "Speaker 1: Hi.\n\nSpeaker 2: Yes."
+        );
+        assert_eq!(
+            drop_repeated_lead("Ça va très bien", "  très bien, merci."),
+            "merci."
+        );
+    }
+
+    #[test]
+    fn labels_speaker_turns_only_once_one_is_detected() {
+        let segment = |text: &str, turn_ends| (text.to_string(), turn_ends);
+        let mut speaker = None;
+        assert_eq!(
+            join_segments(
+                &[segment(" Hello", false), segment(" there.", false)],
+                &mut speaker
+            ),
+            "Hello there."
+        );
+        assert_eq!(speaker, None);
+
+        let text = join_segments(
+            &[
+                segment(" How are you?", true),
+                segment(" Fine.", false),
+                segment(" Thanks.", true),
+            ],
+            &mut speaker,
+        );
+        assert_eq!(text, "Speaker 1: How are you?\n\nSpeaker 2: Fine. Thanks.");
+        // The next segment of the session continues with the other speaker.
+        assert_eq!(
+            join_segments(&[segment(" Good.", false)], &mut speaker),
+            "Speaker 1: Good."
+        );
+    }
+}
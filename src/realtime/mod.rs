@@ -1,5 +1,6 @@
 pub mod audio;
 pub mod events;
+pub mod local;
 pub mod state;
 pub mod transcript;
 pub mod transport;
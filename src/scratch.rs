@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{config_dir, write_json_atomically};
+
+const SCRATCH_FILENAME: &str = "last_session.json";
+
+/// Crash-recovery copy of the transcript being worked on. Unlike an explicit
+/// save it is overwritten as the text changes and restored on the next
+/// launch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScratchSession {
+    pub transcript: String,
+    pub raw_transcript: Option<String>,
+    pub source_transcript: String,
+    pub translated_transcript: String,
+    /// The text had edits that were not saved explicitly; such a copy is
+    /// offered for recovery on launch instead of being restored silently.
+    pub unsaved: bool,
+}
+
+impl ScratchSession {
+    pub fn is_empty(&self) -> bool {
+        [
+            self.transcript.as_str(),
+            self.source_transcript.as_str(),
+            self.translated_transcript.as_str(),
+        ]
+        .iter()
+        .all(|text| text.trim().is_empty())
+    }
+
+    /// The saved session, or `None` when there is none worth restoring.
+    pub fn load() -> Option<Self> {
+        match Self::load_from_path(&default_scratch_path()) {
+            Ok(session) => session.filter(|session| !session.is_empty()),
+            Err(err) => {
+                log::warn!("Failed loading previous session: {err:#}");
+                None
+            }
+        }
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .map(Some)
+                .with_context(|| format!("Invalid JSON in {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).with_context(|| format!("Failed reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.save_to_path(&default_scratch_path())
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        write_json_atomically(self, path)
+    }
+
+    pub fn clear() -> Result<()> {
+        Self::clear_path(&default_scratch_path())
+    }
+
+    pub fn clear_path(path: &Path) -> Result<()> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err).with_context(|| format!("Failed removing {}", path.display())),
+        }
+    }
+}
+
+pub fn default_scratch_path() -> PathBuf {
+    config_dir().join(SCRATCH_FILENAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_clears() {
+        let dir = std::env::temp_dir().join(format!("dictaite-scratch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SCRATCH_FILENAME);
+        assert_eq!(ScratchSession::load_from_path(&path).unwrap(), None);
+
+        let session = ScratchSession {
+            transcript: "Hola".into(),
+            raw_transcript: Some("hola".into()),
+            source_transcript: "Hola".into(),
+            translated_transcript: "Hello".into(),
+            unsaved: true,
+        };
+        session.save_to_path(&path).unwrap();
+        assert_eq!(
+            ScratchSession::load_from_path(&path).unwrap(),
+            Some(session)
+        );
+
+        ScratchSession::clear_path(&path).unwrap();
+        ScratchSession::clear_path(&path).unwrap();
+        assert_eq!(ScratchSession::load_from_path(&path).unwrap(), None);
+        assert!(ScratchSession::default().is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
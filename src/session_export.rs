@@ -0,0 +1,151 @@
+//! Machine-readable record of one session, for scripts that post-process
+//! transcripts. Unlike the plain-text saves it keeps the languages, audio
+//! details and voice alongside the text.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::text_utils::Word;
+
+/// Bumped when a field changes meaning or is removed; new fields do not
+/// bump it.
+pub const SESSION_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionExport {
+    pub version: u32,
+    /// When the export was written, RFC 3339 in UTC.
+    pub exported_at: String,
+    /// Language code chosen for the recording; `None` when auto-detected.
+    pub origin_language: Option<String>,
+    /// Language code reported by the transcription backend, if any.
+    pub detected_language: Option<String>,
+    /// Language code of the translation, when one was made.
+    pub target_language: Option<String>,
+    pub original_text: String,
+    pub translation: Option<String>,
+    /// Empty when the backend reported no word timings.
+    pub words: Vec<WordTiming>,
+    pub duration_secs: Option<f64>,
+    pub sample_rate: Option<u32>,
+    /// TTS voice last used to read the text aloud.
+    pub voice: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub text: String,
+    pub start_secs: f64,
+    pub end_secs: f64,
+}
+
+impl From<&Word> for WordTiming {
+    fn from(word: &Word) -> Self {
+        Self {
+            text: word.text.clone(),
+            start_secs: word.start.as_secs_f64(),
+            end_secs: word.end.as_secs_f64(),
+        }
+    }
+}
+
+impl SessionExport {
+    /// An export stamped with the current time and no optional details.
+    pub fn new(original_text: impl Into<String>) -> Self {
+        Self {
+            version: SESSION_EXPORT_VERSION,
+            exported_at: rfc3339_utc(SystemTime::now()),
+            origin_language: None,
+            detected_language: None,
+            target_language: None,
+            original_text: original_text.into(),
+            translation: None,
+            words: Vec::new(),
+            duration_secs: None,
+            sample_rate: None,
+            voice: None,
+        }
+    }
+
+    pub fn with_duration(mut self, duration: Option<Duration>) -> Self {
+        self.duration_secs = duration.map(|duration| duration.as_secs_f64());
+        self
+    }
+
+    /// Pretty-printed JSON with a trailing newline.
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("session export serializes");
+        json.push('\n');
+        json
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SSZ` for `at`, without pulling in a date crate.
+pub fn rfc3339_utc(at: SystemTime) -> String {
+    let secs = at
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or_default();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Proleptic Gregorian date for a count of days since 1970-01-01, after
+/// Howard Hinnant's `civil_from_days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_utc_timestamps() {
+        assert_eq!(rfc3339_utc(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(951_782_400 + 3_723);
+        assert_eq!(rfc3339_utc(leap_day), "2000-02-29T01:02:03Z");
+        let later = UNIX_EPOCH + Duration::from_secs(1_767_225_599);
+        assert_eq!(rfc3339_utc(later), "2025-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let word = Word {
+            text: "Hola".into(),
+            start: Duration::from_millis(250),
+            end: Duration::from_millis(700),
+            segment: 0,
+        };
+        let export = SessionExport {
+            origin_language: Some("es".into()),
+            target_language: Some("en".into()),
+            translation: Some("Hello".into()),
+            words: vec![WordTiming::from(&word)],
+            sample_rate: Some(24_000),
+            voice: Some("nova".into()),
+            ..SessionExport::new("Hola").with_duration(Some(Duration::from_millis(1_500)))
+        };
+        let json = export.to_json();
+        assert!(json.contains("\"duration_secs\": 1.5"), "{json}");
+        assert!(json.contains("\"detected_language\": null"), "{json}");
+        let parsed: SessionExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, export);
+        assert_eq!(parsed.words[0].start_secs, 0.25);
+    }
+}
@@ -1,12 +1,24 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::audio::{RecordingPreset, WavDepth};
+use crate::constants::{LANGUAGES, VOICE_SAMPLE_TEXT};
+use crate::openai::{HttpConfig, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_REQUEST_TIMEOUT_SECS};
+use crate::realtime::local::{
+    DEFAULT_SEGMENT_OVERLAP_SECS, DEFAULT_SEGMENT_SECS, MIN_SEGMENT_SECS,
+};
+use crate::text_utils::{ParagraphMode, TextPipeline};
+
 const SETTINGS_FILENAME: &str = "settings.json";
 const LEGACY_FILENAME: &str = "dict-ai-te_config.toml";
+const XDG_DIR_NAME: &str = "dict-ai-te";
+pub const DEFAULT_MIN_RECORDING_MS: u64 = 300;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -14,10 +26,255 @@ pub struct Settings {
     #[serde(deserialize_with = "deserialize_optional_lang")]
     pub default_language: Option<String>,
     pub translate_by_default: bool,
+    /// Text translations alternate each source sentence with its
+    /// translation instead of returning the translation alone.
+    pub bilingual_translation: bool,
     #[serde(deserialize_with = "deserialize_optional_lang")]
     pub default_target_language: Option<String>,
     pub female_voice: String,
     pub male_voice: String,
+    /// Language of the Rust app's own labels and messages; `None` follows
+    /// the system locale.
+    pub ui_language: Option<String>,
+    pub transcription_engine: TranscriptionEngine,
+    /// Run transcription output through `format_structured_text`; when off
+    /// the model's text is kept exactly as returned.
+    pub apply_text_formatting: bool,
+    /// How formatting finds paragraph breaks.
+    pub paragraph_mode: ParagraphMode,
+    /// Rewrite spelled-out English numbers as digits in finished segments.
+    pub normalize_numbers: bool,
+    /// High-pass filter audio before transcription to cut rumble and hum.
+    pub reduce_low_frequency_noise: bool,
+    /// Capture format for [`crate::audio::Recorder`]; live sessions always
+    /// stream 24 kHz mono.
+    pub recording_preset: RecordingPreset,
+    /// Directory where [`crate::audio::Recorder`] writes captured audio while
+    /// recording, used by continuous dictation; `None` keeps the clip in
+    /// memory.
+    pub recording_cache_dir: Option<String>,
+    /// Record with [`crate::audio::Recorder`] and transcribe the audio in
+    /// slices cut at pauses instead of streaming it to a live session. Only
+    /// used with the OpenAI engine; see `recording_cache_dir`.
+    pub continuous_dictation: bool,
+    /// Recordings stopped sooner than this are dropped without being
+    /// transcribed, e.g. after an accidental double-click.
+    pub min_recording_ms: u64,
+    /// Depth for [`crate::audio::AudioClip::export_wav`] when a clip was
+    /// recorded rather than loaded from a WAV file.
+    pub export_wav_depth: WavDepth,
+    /// Debugging aid with no UI: when on, every session overwrites
+    /// [`debug_upload_path`] with the exact audio it sent to the API.
+    pub debug_save_upload: bool,
+    pub local_model_path: Option<String>,
+    /// Decoding temperature for the local Whisper engine, 0.0–1.0. Zero
+    /// keeps the model's deterministic default.
+    pub transcribe_temperature: f32,
+    /// Prefix speaker turns with "Speaker N:" when the local model reports
+    /// them; off so single-speaker dictation stays unlabelled.
+    pub speaker_labels: bool,
+    /// Length of the pieces the local engine cuts long recordings into.
+    pub chunk_seconds: u64,
+    /// Audio shared by neighbouring pieces so words at the cut survive;
+    /// always shorter than half of `chunk_seconds`.
+    pub chunk_overlap_seconds: u64,
+    pub request_timeout_secs: u64,
+    pub connect_timeout_secs: u64,
+    pub proxy_url: Option<String>,
+    /// Base URL serving Noto font files and a `SHA256SUMS` list for them,
+    /// used by the "Download fonts" action; `None` hides the action.
+    pub font_download_url: Option<String>,
+    pub last_save_dir: Option<String>,
+    pub last_save_stem: Option<String>,
+    /// TTS voice per language code, used instead of the female/male voice
+    /// when the text being read is known to be in that language.
+    pub language_voices: BTreeMap<String, String>,
+    /// Sentence read aloud when previewing a voice.
+    pub voice_sample_text: String,
+    /// Bring synthesized speech to a common loudness before playback, so
+    /// voices can be compared fairly.
+    pub normalize_tts_loudness: bool,
+    /// Hide the language pickers, translation and playback controls; the
+    /// hidden controls keep their saved defaults.
+    pub compact_view: bool,
+    /// Language codes listed first in the origin and target pickers, in the
+    /// order they were pinned.
+    pub pinned_languages: Vec<String>,
+    /// Language codes a recording may switch between. With two or more the
+    /// spoken language is left to auto-detection and the prompt lists them.
+    pub expected_languages: Vec<String>,
+    /// Instruction passed to the transcription model, e.g. a glossary of
+    /// names; empty sends none.
+    pub transcription_prompt: String,
+    /// Prompt per origin language code, used instead of
+    /// `transcription_prompt` when that language is chosen.
+    pub language_prompts: BTreeMap<String, String>,
+    /// Used only for the rough per-session cost estimate shown in the UI.
+    pub prices: PriceTable,
+}
+
+/// Approximate API prices in US dollars.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PriceTable {
+    pub transcription_per_minute: f64,
+    pub live_translation_per_minute: f64,
+    pub translation_per_1k_chars: f64,
+    pub tts_per_1k_chars: f64,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        Self {
+            transcription_per_minute: 0.006,
+            live_translation_per_minute: 0.06,
+            translation_per_1k_chars: 0.001,
+            tts_per_1k_chars: 0.015,
+        }
+    }
+}
+
+impl PriceTable {
+    /// Cost of a live session of `duration`, transcribing or translating.
+    pub fn live_session_cost(&self, duration: std::time::Duration, translate: bool) -> f64 {
+        let per_minute = if translate {
+            self.live_translation_per_minute
+        } else {
+            self.transcription_per_minute
+        };
+        duration.as_secs_f64() / 60.0 * per_minute
+    }
+
+    /// Cost of translating `chars` characters of input and output combined.
+    pub fn translation_cost(&self, chars: usize) -> f64 {
+        chars as f64 / 1000.0 * self.translation_per_1k_chars
+    }
+
+    pub fn tts_cost(&self, chars: usize) -> f64 {
+        chars as f64 / 1000.0 * self.tts_per_1k_chars
+    }
+
+    fn sanitized(self) -> Self {
+        let defaults = Self::default();
+        let valid = |value: f64, default: f64| {
+            if value.is_finite() && value >= 0.0 {
+                value
+            } else {
+                default
+            }
+        };
+        Self {
+            transcription_per_minute: valid(
+                self.transcription_per_minute,
+                defaults.transcription_per_minute,
+            ),
+            live_translation_per_minute: valid(
+                self.live_translation_per_minute,
+                defaults.live_translation_per_minute,
+            ),
+            translation_per_1k_chars: valid(
+                self.translation_per_1k_chars,
+                defaults.translation_per_1k_chars,
+            ),
+            tts_per_1k_chars: valid(self.tts_per_1k_chars, defaults.tts_per_1k_chars),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptionEngine {
+    #[default]
+    OpenAi,
+    Local,
+}
+
+impl Settings {
+    /// The voice configured for `language`, if any.
+    pub fn voice_for_language(&self, language: &str) -> Option<&str> {
+        self.language_voices
+            .get(&language.trim().to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// The transcription prompt for `language`, falling back to the general
+    /// prompt; `None` when neither is set.
+    pub fn transcription_prompt_for(&self, language: Option<&str>) -> Option<&str> {
+        language
+            .and_then(|language| {
+                self.language_prompts
+                    .get(&language.trim().to_ascii_lowercase())
+            })
+            .map(String::as_str)
+            .or(Some(self.transcription_prompt.as_str()))
+            .map(str::trim)
+            .filter(|prompt| !prompt.is_empty())
+    }
+
+    /// Whether several expected languages are chosen, so the spoken language
+    /// is not pinned to one code.
+    pub fn expects_multiple_languages(&self) -> bool {
+        self.expected_languages.len() > 1
+    }
+
+    /// The transcription prompt for `language` plus, when no language is
+    /// given and several are expected, a line naming them.
+    pub fn transcription_prompt_with_hint(&self, language: Option<&str>) -> Option<String> {
+        let prompt = self.transcription_prompt_for(language);
+        let hint = language
+            .is_none()
+            .then(|| self.expected_languages_hint())
+            .flatten();
+        match (prompt, hint) {
+            (Some(prompt), Some(hint)) => Some(format!("{prompt} {hint}")),
+            (Some(prompt), None) => Some(prompt.to_string()),
+            (None, hint) => hint,
+        }
+    }
+
+    fn expected_languages_hint(&self) -> Option<String> {
+        if !self.expects_multiple_languages() {
+            return None;
+        }
+        let names: Vec<&str> = self
+            .expected_languages
+            .iter()
+            .map(|code| {
+                LANGUAGES
+                    .iter()
+                    .skip(1)
+                    .find(|lang| lang.code == code)
+                    .map_or(code.as_str(), |lang| lang.english_name())
+            })
+            .collect();
+        Some(format!("The audio may contain: {}.", names.join(", ")))
+    }
+
+    pub fn http_config(&self) -> HttpConfig {
+        HttpConfig {
+            request_timeout: std::time::Duration::from_secs(self.request_timeout_secs),
+            connect_timeout: std::time::Duration::from_secs(self.connect_timeout_secs),
+            proxy_url: self.proxy_url.clone(),
+        }
+    }
+
+    /// The clean-up passes enabled for finished transcript text.
+    pub fn text_pipeline(&self) -> TextPipeline {
+        TextPipeline {
+            structure: self.apply_text_formatting,
+            paragraphs: self.paragraph_mode,
+            numbers: self.normalize_numbers,
+        }
+    }
+}
+
+impl TranscriptionEngine {
+    pub fn label(self) -> &'static str {
+        match self {
+            TranscriptionEngine::OpenAi => "OpenAI",
+            TranscriptionEngine::Local => "Local (Whisper)",
+        }
+    }
 }
 
 impl Default for Settings {
@@ -25,9 +282,42 @@ impl Default for Settings {
         Self {
             default_language: None,
             translate_by_default: false,
+            bilingual_translation: false,
             default_target_language: Some("en".to_string()),
             female_voice: "nova".to_string(),
             male_voice: "onyx".to_string(),
+            ui_language: None,
+            transcription_engine: TranscriptionEngine::OpenAi,
+            apply_text_formatting: true,
+            paragraph_mode: ParagraphMode::BlankLines,
+            normalize_numbers: false,
+            reduce_low_frequency_noise: false,
+            recording_preset: RecordingPreset::Voice,
+            recording_cache_dir: None,
+            continuous_dictation: false,
+            min_recording_ms: DEFAULT_MIN_RECORDING_MS,
+            export_wav_depth: WavDepth::Pcm16,
+            debug_save_upload: false,
+            local_model_path: None,
+            transcribe_temperature: 0.0,
+            speaker_labels: false,
+            chunk_seconds: DEFAULT_SEGMENT_SECS,
+            chunk_overlap_seconds: DEFAULT_SEGMENT_OVERLAP_SECS,
+            request_timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT_SECS,
+            proxy_url: None,
+            font_download_url: None,
+            last_save_dir: None,
+            last_save_stem: None,
+            language_voices: BTreeMap::new(),
+            compact_view: false,
+            pinned_languages: Vec::new(),
+            expected_languages: Vec::new(),
+            transcription_prompt: String::new(),
+            language_prompts: BTreeMap::new(),
+            voice_sample_text: VOICE_SAMPLE_TEXT.to_string(),
+            normalize_tts_loudness: false,
+            prices: PriceTable::default(),
         }
     }
 }
@@ -57,11 +347,21 @@ pub fn load_settings_from_path(custom_path: Option<&Path>) -> Result<Settings> {
         .unwrap_or_else(default_settings_path);
 
     match fs::read_to_string(&settings_path) {
-        Ok(raw) => {
-            let parsed: Settings = serde_json::from_str(&raw)
-                .with_context(|| format!("Invalid JSON in {}", settings_path.display()))?;
-            Ok(fill_defaults(parsed))
-        }
+        Ok(raw) => match serde_json::from_str::<Settings>(&raw) {
+            Ok(parsed) => Ok(fill_defaults(parsed)),
+            Err(err) => {
+                let backup = with_suffix(&settings_path, ".bak");
+                log::warn!(
+                    "Invalid JSON in {} ({err}); moving it to {} and using defaults",
+                    settings_path.display(),
+                    backup.display()
+                );
+                fs::rename(&settings_path, &backup).with_context(|| {
+                    format!("Failed backing up corrupt {}", settings_path.display())
+                })?;
+                Ok(Settings::default())
+            }
+        },
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
             if let Some(legacy) = load_legacy_settings()? {
                 save_settings_to_path(&legacy, &settings_path)?;
@@ -74,25 +374,66 @@ pub fn load_settings_from_path(custom_path: Option<&Path>) -> Result<Settings> {
     }
 }
 
+/// Reads a settings file chosen by the user. Unlike the regular loader this
+/// never touches the file and reports invalid JSON instead of falling back.
+pub fn import_settings(path: &Path) -> Result<Settings> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed reading {}", path.display()))?;
+    let parsed: Settings = serde_json::from_str(&raw)
+        .with_context(|| format!("Invalid settings JSON in {}", path.display()))?;
+    Ok(fill_defaults(parsed))
+}
+
 pub fn save_settings(settings: &Settings) -> Result<()> {
     let path = default_settings_path();
     save_settings_to_path(settings, &path)
 }
 
 pub fn save_settings_to_path(settings: &Settings, path: &Path) -> Result<()> {
+    write_json_atomically(settings, path)
+}
+
+pub(crate) fn write_json_atomically<T: Serialize>(value: &T, path: &Path) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed creating {}", parent.display()))?;
     }
-    let payload =
-        serde_json::to_string_pretty(settings).context("Failed serializing settings to JSON")?;
-    fs::write(path, payload).with_context(|| format!("Failed writing {}", path.display()))
+    let payload = serde_json::to_string_pretty(value).context("Failed serializing to JSON")?;
+    // Write next to the target and rename into place so a crash mid-write
+    // never leaves a truncated settings file behind.
+    let temp_path = with_suffix(path, ".tmp");
+    let write_result = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(payload.as_bytes())?;
+        file.sync_all()
+    })();
+    if let Err(err) = write_result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(err).with_context(|| format!("Failed writing {}", temp_path.display()));
+    }
+    fs::rename(&temp_path, path).with_context(|| format!("Failed replacing {}", path.display()))
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
 }
 
 pub fn default_settings_path() -> PathBuf {
     config_dir().join(SETTINGS_FILENAME)
 }
 
+/// Where sessions save their uploaded audio when
+/// [`Settings::debug_save_upload`] is on.
+pub fn debug_upload_path() -> PathBuf {
+    config_dir().join("debug").join("last_upload.wav")
+}
+
+/// Resolves the settings directory: `DICTAITE_HOME` wins, then an existing
+/// `~/.dictaite`, which the Python variants share, then
+/// `$XDG_CONFIG_HOME/dict-ai-te` on Linux and other XDG platforms, and
+/// finally `~/.dictaite`.
 pub fn config_dir() -> PathBuf {
     if let Ok(custom) = env::var("DICTAITE_HOME") {
         let path = PathBuf::from(custom);
@@ -100,6 +441,19 @@ pub fn config_dir() -> PathBuf {
             return path;
         }
     }
+    let home = home_config_dir();
+    if cfg!(not(any(target_os = "macos", target_os = "windows"))) && !home.is_dir() {
+        if let Ok(xdg) = env::var("XDG_CONFIG_HOME") {
+            let path = PathBuf::from(xdg);
+            if path.is_absolute() {
+                return path.join(XDG_DIR_NAME);
+            }
+        }
+    }
+    home
+}
+
+fn home_config_dir() -> PathBuf {
     dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join(".dictaite")
@@ -152,7 +506,7 @@ fn legacy_settings_path() -> PathBuf {
                 .unwrap_or_else(|| PathBuf::from("."))
                 .join(".config")
         });
-    dir.join("dict-ai-te").join(LEGACY_FILENAME)
+    dir.join(XDG_DIR_NAME).join(LEGACY_FILENAME)
 }
 
 fn normalize_optional(value: &str) -> Option<String> {
@@ -164,7 +518,19 @@ fn normalize_optional(value: &str) -> Option<String> {
     }
 }
 
-fn fill_defaults(mut settings: Settings) -> Settings {
+/// Trimmed, lowercased language codes without blanks or repeats, in order.
+fn unique_codes(codes: Vec<String>) -> Vec<String> {
+    let mut unique = Vec::new();
+    for code in codes {
+        let code = code.trim().to_ascii_lowercase();
+        if !code.is_empty() && !unique.contains(&code) {
+            unique.push(code);
+        }
+    }
+    unique
+}
+
+pub(crate) fn fill_defaults(mut settings: Settings) -> Settings {
     if settings.female_voice.trim().is_empty() {
         settings.female_voice = "nova".to_string();
     } else {
@@ -180,6 +546,66 @@ fn fill_defaults(mut settings: Settings) -> Settings {
             settings.default_language = None;
         }
     }
+    settings.ui_language = settings
+        .ui_language
+        .as_deref()
+        .map(|code| code.trim().to_ascii_lowercase())
+        .filter(|code| !code.is_empty());
+    if settings.request_timeout_secs == 0 {
+        settings.request_timeout_secs = DEFAULT_REQUEST_TIMEOUT_SECS;
+    }
+    if settings.connect_timeout_secs == 0 {
+        settings.connect_timeout_secs = DEFAULT_CONNECT_TIMEOUT_SECS;
+    }
+    settings.chunk_seconds = settings.chunk_seconds.max(MIN_SEGMENT_SECS);
+    if settings.chunk_overlap_seconds * 2 >= settings.chunk_seconds {
+        settings.chunk_overlap_seconds = DEFAULT_SEGMENT_OVERLAP_SECS;
+    }
+    if settings
+        .local_model_path
+        .as_ref()
+        .is_some_and(|path| path.trim().is_empty())
+    {
+        settings.local_model_path = None;
+    }
+    settings.transcribe_temperature = if settings.transcribe_temperature.is_finite() {
+        settings.transcribe_temperature.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    settings.language_voices = std::mem::take(&mut settings.language_voices)
+        .into_iter()
+        .map(|(lang, voice)| {
+            (
+                lang.trim().to_ascii_lowercase(),
+                voice.trim().to_ascii_lowercase(),
+            )
+        })
+        .filter(|(lang, voice)| !lang.is_empty() && !voice.is_empty())
+        .collect();
+    settings.language_prompts = std::mem::take(&mut settings.language_prompts)
+        .into_iter()
+        .map(|(lang, prompt)| (lang.trim().to_ascii_lowercase(), prompt.trim().to_string()))
+        .filter(|(lang, prompt)| !lang.is_empty() && !prompt.is_empty())
+        .collect();
+    settings.pinned_languages = unique_codes(std::mem::take(&mut settings.pinned_languages));
+    settings.expected_languages = unique_codes(std::mem::take(&mut settings.expected_languages));
+    if settings.voice_sample_text.trim().is_empty() {
+        settings.voice_sample_text = VOICE_SAMPLE_TEXT.to_string();
+    }
+    settings.prices = std::mem::take(&mut settings.prices).sanitized();
+    settings.proxy_url = settings
+        .proxy_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string);
+    settings.font_download_url = settings
+        .font_download_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string);
     if let Some(ref mut lang) = settings.default_target_language {
         if lang.trim().is_empty() {
             settings.default_target_language = Some("en".to_string());
@@ -187,3 +613,316 @@ fn fill_defaults(mut settings: Settings) -> Settings {
     }
     settings
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Runs `check` with the given variables and `HOME` pointing at `home`.
+    fn with_env<F: FnOnce()>(
+        home: &Path,
+        dictaite_home: Option<&str>,
+        xdg: Option<&str>,
+        check: F,
+    ) {
+        let _guard = ENV_LOCK.lock();
+        let saved = (
+            env::var("DICTAITE_HOME"),
+            env::var("XDG_CONFIG_HOME"),
+            env::var("HOME"),
+        );
+        env::set_var("HOME", home);
+        match dictaite_home {
+            Some(value) => env::set_var("DICTAITE_HOME", value),
+            None => env::remove_var("DICTAITE_HOME"),
+        }
+        match xdg {
+            Some(value) => env::set_var("XDG_CONFIG_HOME", value),
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        check();
+        match saved.0 {
+            Ok(value) => env::set_var("DICTAITE_HOME", value),
+            Err(_) => env::remove_var("DICTAITE_HOME"),
+        }
+        match saved.1 {
+            Ok(value) => env::set_var("XDG_CONFIG_HOME", value),
+            Err(_) => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        match saved.2 {
+            Ok(value) => env::set_var("HOME", value),
+            Err(_) => env::remove_var("HOME"),
+        }
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("dictaite-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn saves_atomically_and_round_trips() {
+        let dir = scratch_dir("atomic");
+        let path = dir.join(SETTINGS_FILENAME);
+        let settings = Settings {
+            female_voice: "alloy".into(),
+            ..Settings::default()
+        };
+        save_settings_to_path(&settings, &path).unwrap();
+        assert!(!with_suffix(&path, ".tmp").exists());
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.female_voice, "alloy");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_chunk_overlap_below_the_chunk_length() {
+        let dir = scratch_dir("chunks");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(
+            &path,
+            r#"{"chunk_seconds": 120, "chunk_overlap_seconds": 10}"#,
+        )
+        .unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(
+            (loaded.chunk_seconds, loaded.chunk_overlap_seconds),
+            (120, 10)
+        );
+
+        fs::write(
+            &path,
+            r#"{"chunk_seconds": 5, "chunk_overlap_seconds": 20}"#,
+        )
+        .unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.chunk_seconds, MIN_SEGMENT_SECS);
+        assert_eq!(loaded.chunk_overlap_seconds, DEFAULT_SEGMENT_OVERLAP_SECS);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn older_files_get_default_timeouts() {
+        let dir = scratch_dir("timeouts");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(
+            &path,
+            r#"{"female_voice": "nova", "connect_timeout_secs": 0}"#,
+        )
+        .unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.request_timeout_secs, DEFAULT_REQUEST_TIMEOUT_SECS);
+        assert_eq!(loaded.connect_timeout_secs, DEFAULT_CONNECT_TIMEOUT_SECS);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn clamps_transcribe_temperature() {
+        let mut settings = Settings {
+            transcribe_temperature: 1.5,
+            ..Settings::default()
+        };
+        settings = fill_defaults(settings);
+        assert_eq!(settings.transcribe_temperature, 1.0);
+        settings.transcribe_temperature = -0.2;
+        assert_eq!(fill_defaults(settings).transcribe_temperature, 0.0);
+    }
+
+    #[test]
+    fn normalizes_language_voices() {
+        let dir = scratch_dir("language-voices");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(
+            &path,
+            r#"{"language_voices": {" DE ": "Onyx", "fr": " ", "": "nova"}}"#,
+        )
+        .unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.language_voices.len(), 1);
+        assert_eq!(loaded.voice_for_language("de"), Some("onyx"));
+        assert_eq!(loaded.voice_for_language("fr"), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn normalizes_pinned_languages() {
+        let dir = scratch_dir("pinned-languages");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(&path, r#"{"pinned_languages": [" NL ", "es", "nl", ""]}"#).unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.pinned_languages, vec!["nl", "es"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn hints_at_several_expected_languages() {
+        let mut settings = Settings {
+            expected_languages: vec!["en".into(), "es".into()],
+            ..Settings::default()
+        };
+        assert!(settings.expects_multiple_languages());
+        assert_eq!(
+            settings.transcription_prompt_with_hint(None).as_deref(),
+            Some("The audio may contain: English, Spanish.")
+        );
+        assert_eq!(settings.transcription_prompt_with_hint(Some("en")), None);
+
+        settings.transcription_prompt = "Names: Bjørn.".into();
+        settings.expected_languages.push("zh".into());
+        assert_eq!(
+            settings.transcription_prompt_with_hint(None).as_deref(),
+            Some("Names: Bjørn. The audio may contain: English, Spanish, Chinese.")
+        );
+
+        settings.expected_languages.truncate(1);
+        assert!(!settings.expects_multiple_languages());
+        assert_eq!(
+            settings.transcription_prompt_with_hint(None).as_deref(),
+            Some("Names: Bjørn.")
+        );
+    }
+
+    #[test]
+    fn picks_language_prompt_before_the_general_one() {
+        let dir = scratch_dir("language-prompts");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(
+            &path,
+            r#"{"transcription_prompt": "Names: Anaïs, Bjørn.", "language_prompts": {" JA ": "Do not insert spaces between Japanese words.", "fr": " "}}"#,
+        )
+        .unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.language_prompts.len(), 1);
+        assert_eq!(
+            loaded.transcription_prompt_for(Some("ja")),
+            Some("Do not insert spaces between Japanese words.")
+        );
+        assert_eq!(
+            loaded.transcription_prompt_for(Some("fr")),
+            Some("Names: Anaïs, Bjørn.")
+        );
+        assert_eq!(
+            loaded.transcription_prompt_for(None),
+            Some("Names: Anaïs, Bjørn.")
+        );
+        assert_eq!(
+            Settings::default().transcription_prompt_for(Some("ja")),
+            None
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn estimates_costs_and_rejects_negative_prices() {
+        let dir = scratch_dir("prices");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(
+            &path,
+            r#"{"prices": {"tts_per_1k_chars": -1.0, "transcription_per_minute": 0.01}}"#,
+        )
+        .unwrap();
+        let prices = load_settings_from_path(Some(&path)).unwrap().prices;
+        assert_eq!(
+            prices.tts_per_1k_chars,
+            PriceTable::default().tts_per_1k_chars
+        );
+        let cost = prices.live_session_cost(std::time::Duration::from_secs(90), false);
+        assert!((cost - 0.015).abs() < 1e-9);
+        assert!((prices.tts_cost(2_000) - 0.03).abs() < 1e-9);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn backs_up_truncated_file_and_uses_defaults() {
+        let dir = scratch_dir("corrupt");
+        let path = dir.join(SETTINGS_FILENAME);
+        fs::write(&path, "{\"female_voice\": \"all").unwrap();
+        let loaded = load_settings_from_path(Some(&path)).unwrap();
+        assert_eq!(loaded.female_voice, Settings::default().female_voice);
+        assert!(!path.exists());
+        let backup = dir.join("settings.json.bak");
+        assert_eq!(
+            fs::read_to_string(backup).unwrap(),
+            "{\"female_voice\": \"all"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn import_rejects_invalid_json_without_moving_it() {
+        let dir = scratch_dir("import");
+        let path = dir.join("exported.json");
+        fs::write(&path, "not json").unwrap();
+        assert!(import_settings(&path).is_err());
+        assert!(path.exists());
+        fs::write(&path, r#"{"male_voice": " Echo "}"#).unwrap();
+        assert_eq!(import_settings(&path).unwrap().male_voice, "echo");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dictaite_home_overrides_xdg() {
+        let home = scratch_dir("home-override");
+        with_env(&home, Some("/tmp/dictaite-home"), Some("/tmp/xdg"), || {
+            assert_eq!(config_dir(), PathBuf::from("/tmp/dictaite-home"));
+        });
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    #[test]
+    fn honors_xdg_config_home() {
+        let home = scratch_dir("home-xdg");
+        with_env(&home, None, Some("/tmp/xdg"), || {
+            assert_eq!(config_dir(), PathBuf::from("/tmp/xdg/dict-ai-te"));
+            assert_eq!(
+                default_settings_path(),
+                PathBuf::from("/tmp/xdg/dict-ai-te/settings.json")
+            );
+        });
+        fs::remove_dir_all(home).unwrap();
+    }
+
+    #[test]
+    fn keeps_the_shared_home_dir_when_it_exists() {
+        let home = scratch_dir("home-shared");
+        let xdg = scratch_dir("xdg-shared");
+        fs::create_dir_all(home.join(".dictaite")).unwrap();
+        fs::write(
+            home.join(".dictaite").join(SETTINGS_FILENAME),
+            r#"{"female_voice": "shimmer"}"#,
+        )
+        .unwrap();
+        with_env(&home, None, xdg.to_str(), || {
+            assert_eq!(config_dir(), home.join(".dictaite"));
+            assert_eq!(load_settings().female_voice, "shimmer");
+            let settings = Settings {
+                female_voice: "alloy".into(),
+                ..Settings::default()
+            };
+            save_settings(&settings).unwrap();
+        });
+        let saved = fs::read_to_string(home.join(".dictaite").join(SETTINGS_FILENAME)).unwrap();
+        assert!(saved.contains("alloy"), "{saved}");
+        assert!(!xdg.join(XDG_DIR_NAME).exists());
+        fs::remove_dir_all(home).unwrap();
+        fs::remove_dir_all(xdg).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_home_when_unset_or_relative() {
+        let home = scratch_dir("home-fallback");
+        with_env(&home, Some("relative"), Some("also-relative"), || {
+            assert_eq!(config_dir(), home_config_dir());
+        });
+        with_env(&home, None, None, || {
+            assert_eq!(config_dir(), home_config_dir());
+        });
+        fs::remove_dir_all(home).unwrap();
+    }
+}
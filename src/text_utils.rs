@@ -1,10 +1,60 @@
+use std::time::Duration;
+
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 static PARA_SPLIT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n\s*\n").unwrap());
 static SPACE_COLLAPSE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+static WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z]+(?:-[A-Za-z]+)*").unwrap());
+/// Fenced blocks (``` or ~~~, fences on their own lines), then ``double``
+/// and `single` backtick inline code.
+static CODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ms)^[ \t]*```.*?^[ \t]*```[ \t]*$|^[ \t]*~~~.*?^[ \t]*~~~[ \t]*$|``[^\n]+?``|`[^`\n]+`",
+    )
+    .unwrap()
+});
+static CODE_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"⟦C(\d+)⟧").unwrap());
+
+/// Runs of identical sentences at least this long are treated as a model
+/// loop and collapsed; shorter runs are kept as genuine repetition.
+const MIN_HALLUCINATED_REPEATS: usize = 3;
+/// [`ParagraphMode::Sentences`] keeps adding sentences to a paragraph until
+/// it is at least this long.
+const PARAGRAPH_TARGET_CHARS: usize = 300;
+
+/// How [`format_structured_text_with`] decides where paragraphs end.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParagraphMode {
+    /// Only blank lines separate paragraphs; other line breaks are joined.
+    #[default]
+    BlankLines,
+    /// Blank lines still separate paragraphs, and in between sentences are
+    /// grouped into paragraphs of a few hundred characters. A paragraph only
+    /// ends after sentence-ending punctuation followed by a capital letter.
+    Sentences,
+}
+
+impl ParagraphMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            ParagraphMode::BlankLines => "Blank lines only",
+            ParagraphMode::Sentences => "Group sentences",
+        }
+    }
+}
 
+/// Normalizes whitespace, keeps paragraph breaks and drops looped sentences
+/// (see [`collapse_repeated_sentences`]).
 pub fn format_structured_text(text: &str) -> String {
+    format_structured_text_with(text, ParagraphMode::BlankLines)
+}
+
+/// [`format_structured_text`] with a choice of paragraph detection, for
+/// transcripts that put every sentence on its own line or none on a new one.
+pub fn format_structured_text_with(text: &str, mode: ParagraphMode) -> String {
     let trimmed = text.trim();
     if trimmed.is_empty() {
         return String::new();
@@ -28,8 +78,747 @@ pub fn format_structured_text(text: &str) -> String {
         if lines.is_empty() {
             continue;
         }
-        paragraphs.push(lines.join(" "));
+        let block = collapse_repeated_sentences(&lines.join(" "));
+        match mode {
+            ParagraphMode::BlankLines => paragraphs.push(block),
+            ParagraphMode::Sentences => paragraphs.extend(group_sentences(&block)),
+        }
     }
 
     paragraphs.join("\n\n")
 }
+
+/// Splits one paragraph into paragraphs of at least
+/// [`PARAGRAPH_TARGET_CHARS`], breaking only before a sentence that starts
+/// with a capital letter.
+fn group_sentences(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    for sentence in split_sentences(text) {
+        let starts_capitalized = sentence
+            .trim_start()
+            .chars()
+            .next()
+            .is_some_and(char::is_uppercase);
+        let ends_sentence = current
+            .trim_end()
+            .chars()
+            .last()
+            .is_some_and(is_sentence_end);
+        if current.trim().len() >= PARAGRAPH_TARGET_CHARS && ends_sentence && starts_capitalized {
+            paragraphs.push(current.trim().to_string());
+            current.clear();
+        }
+        current.push_str(sentence);
+    }
+    if !current.trim().is_empty() {
+        paragraphs.push(current.trim().to_string());
+    }
+    paragraphs
+}
+
+/// The blank-line separated paragraphs of `text`, trimmed, without empty ones.
+pub fn split_paragraphs(text: &str) -> Vec<&str> {
+    PARA_SPLIT
+        .split(text)
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect()
+}
+
+/// Joins the whole text into one line, collapsing every run of whitespace
+/// (paragraph breaks included) into a single space.
+pub fn flatten_text(text: &str) -> String {
+    SPACE_COLLAPSE.replace_all(text.trim(), " ").into_owned()
+}
+
+/// Replaces fenced code blocks and inline `code` with placeholders such as
+/// `⟦C1⟧`, so the prose can go through a translator without the code being
+/// touched. Returns the text and the code spans in placeholder order.
+pub fn protect_code(text: &str) -> (String, Vec<String>) {
+    let mut code = Vec::new();
+    let protected = CODE.replace_all(text, |caps: &regex::Captures| {
+        code.push(caps[0].to_string());
+        format!("⟦C{}⟧", code.len())
+    });
+    (protected.into_owned(), code)
+}
+
+/// Undoes [`protect_code`]. Spans whose placeholder went missing are
+/// appended as paragraphs of their own, so no code is ever lost.
+pub fn restore_code(text: &str, code: &[String]) -> String {
+    let mut used = vec![false; code.len()];
+    let mut restored = CODE_PLACEHOLDER
+        .replace_all(text, |caps: &regex::Captures| {
+            let index = caps[1].parse::<usize>().unwrap_or(0);
+            match index.checked_sub(1).and_then(|idx| code.get(idx)) {
+                Some(span) => {
+                    used[index - 1] = true;
+                    span.clone()
+                }
+                None => caps[0].to_string(),
+            }
+        })
+        .into_owned();
+    for (span, used) in code.iter().zip(used) {
+        if !used {
+            restored.push_str("\n\n");
+            restored.push_str(span);
+        }
+    }
+    restored
+}
+
+/// Trimmed sentences of `text`, paragraph by paragraph.
+pub fn split_sentence_units(text: &str) -> Vec<&str> {
+    split_paragraphs(text)
+        .into_iter()
+        .flat_map(split_sentences)
+        .map(str::trim)
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+/// Alternates each source sentence with its translation, one pair per
+/// paragraph. A missing translation leaves the source sentence on its own.
+pub fn interleave_bilingual(sources: &[&str], translations: &[String]) -> String {
+    sources
+        .iter()
+        .enumerate()
+        .map(
+            |(idx, source)| match translations.get(idx).map(|text| text.trim()) {
+                Some(translation) if !translation.is_empty() => format!("{source}\n{translation}"),
+                _ => source.to_string(),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// One transcribed word with its timing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    pub text: String,
+    /// Offset from the start of the recording.
+    pub start: Duration,
+    pub end: Duration,
+    /// Index of the model segment the word was returned in.
+    pub segment: usize,
+}
+
+/// Where [`with_timestamps`] starts a new timestamped line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimestampGranularity {
+    /// After each word that ends a sentence.
+    #[default]
+    Sentence,
+    /// Whenever the model segment changes.
+    Segment,
+}
+
+/// Words as note-friendly lines, each prefixed with the time its first word
+/// starts: `[00:12] word word word`. Hours are added once the recording runs
+/// past one. Unlike subtitles there are no end times or numbering.
+pub fn with_timestamps(words: &[Word], granularity: TimestampGranularity) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    let mut previous: Option<&Word> = None;
+    for word in words {
+        let text = word.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let breaks = previous.is_some_and(|previous| match granularity {
+            TimestampGranularity::Sentence => ends_sentence(&previous.text),
+            TimestampGranularity::Segment => previous.segment != word.segment,
+        });
+        if breaks {
+            lines.push(std::mem::take(&mut line));
+        }
+        if line.is_empty() {
+            line = format!("[{}] {text}", format_timestamp(word.start));
+        } else {
+            line.push(' ');
+            line.push_str(text);
+        }
+        previous = Some(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.trim_end()
+        .trim_end_matches(['"', '\'', '”', '’', ')', '»'])
+        .chars()
+        .next_back()
+        .is_some_and(is_sentence_end)
+}
+
+/// `MM:SS`, or `H:MM:SS` from one hour on.
+fn format_timestamp(at: Duration) -> String {
+    let secs = at.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+/// The optional clean-up passes run over finished transcript text, usually
+/// taken from [`crate::settings::Settings::text_pipeline`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextPipeline {
+    /// Run [`format_structured_text_with`].
+    pub structure: bool,
+    /// Paragraph detection for the structure pass.
+    pub paragraphs: ParagraphMode,
+    /// Run [`normalize_numbers`].
+    pub numbers: bool,
+}
+
+impl TextPipeline {
+    pub fn apply(&self, text: &str) -> String {
+        let text = if self.structure {
+            format_structured_text_with(text, self.paragraphs)
+        } else {
+            text.to_string()
+        };
+        if self.numbers {
+            normalize_numbers(&text)
+        } else {
+            text
+        }
+    }
+}
+
+/// Rewrites spelled-out English numbers as digits ("three hundred and
+/// twelve" → "312", "twenty twenty five" → "2025"). Deliberately
+/// conservative: single words below ten ("one of the"), runs next to an
+/// ordinal ("twenty first") and anything that is not a well-formed number
+/// ("one and two", "a hundred") are left as written.
+pub fn normalize_numbers(text: &str) -> String {
+    let words: Vec<_> = WORD.find_iter(text).collect();
+    let mut output = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut idx = 0;
+    while idx < words.len() {
+        let starts_run = is_number_token(words[idx].as_str())
+            && !(idx > 0
+                && adjacent(text, &words[idx - 1], &words[idx])
+                && is_ordinal_token(words[idx - 1].as_str()));
+        if !starts_run {
+            idx += 1;
+            continue;
+        }
+        let mut end = idx + 1;
+        while end < words.len()
+            && adjacent(text, &words[end - 1], &words[end])
+            && (is_number_token(words[end].as_str())
+                || words[end].as_str().eq_ignore_ascii_case("and"))
+        {
+            end += 1;
+        }
+        while words[end - 1].as_str().eq_ignore_ascii_case("and") {
+            end -= 1;
+        }
+        let next_is_ordinal = end < words.len()
+            && adjacent(text, &words[end - 1], &words[end])
+            && is_ordinal_token(words[end].as_str());
+        let tokens: Vec<String> = words[idx..end]
+            .iter()
+            .flat_map(|word| word.as_str().split('-'))
+            .map(str::to_ascii_lowercase)
+            .collect();
+        let value = parse_cardinal(&tokens).or_else(|| parse_year(&tokens));
+        if let Some(value) =
+            value.filter(|value| !next_is_ordinal && (tokens.len() > 1 || *value >= 10))
+        {
+            output.push_str(&text[copied..words[idx].start()]);
+            output.push_str(&value.to_string());
+            copied = words[end - 1].end();
+        }
+        idx = end;
+    }
+    output.push_str(&text[copied..]);
+    output
+}
+
+const UNITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: [(&str, u64); 3] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+const ORDINALS: [&str; 13] = [
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "twelfth",
+    "hundredth",
+    "thousandth",
+    "millionth",
+];
+
+/// Only spaces between the two words; punctuation or a line break ends a number.
+fn adjacent(text: &str, left: &regex::Match, right: &regex::Match) -> bool {
+    text[left.end()..right.start()]
+        .chars()
+        .all(|ch| ch == ' ' || ch == '\t')
+        && left.end() < right.start()
+}
+
+fn is_number_token(token: &str) -> bool {
+    token.split('-').all(|part| {
+        let part = part.to_ascii_lowercase();
+        part == "hundred"
+            || UNITS.contains(&part.as_str())
+            || TEENS.contains(&part.as_str())
+            || TENS.contains(&part.as_str())
+            || SCALES.iter().any(|(name, _)| *name == part)
+    })
+}
+
+fn is_ordinal_token(token: &str) -> bool {
+    token.split('-').any(|part| {
+        let part = part.to_ascii_lowercase();
+        ORDINALS.contains(&part.as_str())
+            || part
+                .strip_suffix("ieth")
+                .is_some_and(|stem| TENS.contains(&format!("{stem}y").as_str()))
+            || part
+                .strip_suffix("th")
+                .is_some_and(|stem| TEENS.contains(&stem) || UNITS.contains(&stem))
+    })
+}
+
+fn word_value(word: &str, table: &[&str], base: u64) -> Option<u64> {
+    table
+        .iter()
+        .position(|name| *name == word)
+        .map(|idx| base + idx as u64)
+}
+
+/// Parses 0–99 from the front of `tokens`, returning the value and the
+/// number of tokens used.
+fn parse_below_hundred(tokens: &[String]) -> Option<(u64, usize)> {
+    let first = tokens.first()?;
+    if let Some(value) = word_value(first, &TEENS, 10) {
+        return Some((value, 1));
+    }
+    if let Some(value) = word_value(first, &UNITS, 0) {
+        return Some((value, 1));
+    }
+    let tens = (word_value(first, &TENS, 2)?) * 10;
+    match tokens.get(1).and_then(|next| word_value(next, &UNITS, 0)) {
+        Some(unit) if unit > 0 => Some((tens + unit, 2)),
+        _ => Some((tens, 1)),
+    }
+}
+
+/// Parses 0–999 ("nineteen hundred" included) from the front of `tokens`.
+fn parse_below_thousand(tokens: &[String]) -> Option<(u64, usize)> {
+    let (lead, used) = parse_below_hundred(tokens)?;
+    if tokens.get(used).map(String::as_str) != Some("hundred") || lead == 0 {
+        return Some((lead, used));
+    }
+    let mut used = used + 1;
+    let rest_start = used + usize::from(tokens.get(used).map(String::as_str) == Some("and"));
+    match parse_below_hundred(&tokens[rest_start.min(tokens.len())..]) {
+        Some((rest, rest_used)) if rest > 0 => {
+            used = rest_start + rest_used;
+            Some((lead * 100 + rest, used))
+        }
+        _ => Some((lead * 100, used)),
+    }
+}
+
+/// The value of `tokens` when the whole run is one well-formed cardinal.
+fn parse_cardinal(tokens: &[String]) -> Option<u64> {
+    let mut total = 0;
+    let mut pos = 0;
+    let mut last_scale = u64::MAX;
+    loop {
+        if pos > 0 && tokens.get(pos).map(String::as_str) == Some("and") {
+            pos += 1;
+        }
+        let (group, used) = parse_below_thousand(&tokens[pos..])?;
+        pos += used;
+        let scale = tokens
+            .get(pos)
+            .and_then(|word| SCALES.iter().find(|(name, _)| name == word))
+            .map(|(_, scale)| *scale);
+        match scale {
+            Some(scale) if scale < last_scale && group > 0 => {
+                total += group * scale;
+                last_scale = scale;
+                pos += 1;
+                if pos == tokens.len() {
+                    return Some(total);
+                }
+            }
+            Some(_) => return None,
+            None => return (pos == tokens.len()).then_some(total + group),
+        }
+    }
+}
+
+/// Two two-digit groups read as a year: "nineteen eighty four" → 1984.
+fn parse_year(tokens: &[String]) -> Option<u64> {
+    let (century, used) = parse_below_hundred(tokens)?;
+    let (rest, rest_used) = parse_below_hundred(&tokens[used..])?;
+    (used + rest_used == tokens.len() && (10..100).contains(&century) && (10..100).contains(&rest))
+        .then_some(century * 100 + rest)
+}
+
+/// Collapses three or more consecutive identical sentences into one, as
+/// produced when a transcription model loops over silence ("Thank you. Thank
+/// you. Thank you."). Sentences compare case- and punctuation-insensitively.
+pub fn collapse_repeated_sentences(text: &str) -> String {
+    let sentences = split_sentences(text);
+    let mut output = String::with_capacity(text.len());
+    let mut idx = 0;
+    while idx < sentences.len() {
+        let key = sentence_key(sentences[idx]);
+        let mut end = idx + 1;
+        while !key.is_empty() && end < sentences.len() && sentence_key(sentences[end]) == key {
+            end += 1;
+        }
+        if end - idx >= MIN_HALLUCINATED_REPEATS {
+            output.push_str(sentences[idx]);
+        } else {
+            sentences[idx..end]
+                .iter()
+                .for_each(|sentence| output.push_str(sentence));
+        }
+        idx = end;
+    }
+    output.trim_end().to_string()
+}
+
+/// Splits after sentence-ending punctuation, keeping the punctuation and any
+/// following whitespace with the sentence so the pieces concatenate back to
+/// the input.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, ch)) = chars.next() {
+        let boundary = match ch {
+            '。' | '！' | '？' => true,
+            // "1.2.3" or "e.g" are not sentence ends.
+            '.' | '!' | '?' | '…' => chars
+                .peek()
+                .is_none_or(|(_, next)| next.is_whitespace() || is_sentence_end(*next)),
+            _ => false,
+        };
+        if !boundary {
+            continue;
+        }
+        while chars
+            .peek()
+            .is_some_and(|(_, next)| next.is_whitespace() || is_sentence_end(*next))
+        {
+            chars.next();
+        }
+        let end = chars.peek().map_or(text.len(), |(idx, _)| *idx);
+        sentences.push(&text[start..end]);
+        start = end;
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+fn is_sentence_end(ch: char) -> bool {
+    matches!(ch, '.' | '!' | '?' | '…' | '。' | '！' | '？')
+}
+
+fn sentence_key(sentence: &str) -> String {
+    sentence
+        .chars()
+        .filter(|ch| ch.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn protects_and_restores_code_blocks_and_inline_code() {
+        let text = "Run `cargo test` first.\n\n```rust\nlet x = 1; // `inline`\n```\n\nThen ``a ` b`` ends.";
+        let (protected, code) = protect_code(text);
+        assert_eq!(protected, "Run ⟦C1⟧ first.\n\n⟦C2⟧\n\nThen ⟦C3⟧ ends.");
+        assert_eq!(code[1], "```rust\nlet x = 1; // `inline`\n```");
+        assert_eq!(restore_code(&protected, &code), text);
+
+        let translated = "Ejecuta ⟦C1⟧ primero.\n\nLuego ⟦C3⟧ termina.";
+        assert_eq!(
+            restore_code(translated, &code),
+            "Ejecuta `cargo test` primero.\n\nLuego ``a ` b`` termina.\n\n```rust\nlet x = 1; // `inline`\n```"
+        );
+        assert_eq!(
+            protect_code("No code here."),
+            ("No code here.".to_string(), vec![])
+        );
+    }
+
+    /// Fourteen sentences of about 60 characters each, one per line.
+    fn sentence_per_line() -> String {
+        (1..=14)
+            .map(|n| format!("Sentence {n:02} talks about the quarterly plan in some detail."))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn groups_line_per_sentence_transcripts_into_paragraphs() {
+        let text = sentence_per_line();
+        let blank_lines = format_structured_text_with(&text, ParagraphMode::BlankLines);
+        assert!(!blank_lines.contains("\n"));
+
+        let grouped = format_structured_text_with(&text, ParagraphMode::Sentences);
+        let paragraphs: Vec<&str> = grouped.split("\n\n").collect();
+        assert_eq!(paragraphs.len(), 3);
+        assert!(paragraphs[0].starts_with("Sentence 01") && paragraphs[0].ends_with("detail."));
+        assert!(paragraphs[..2]
+            .iter()
+            .all(|paragraph| paragraph.len() >= PARAGRAPH_TARGET_CHARS));
+        assert_eq!(
+            format_structured_text_with(&grouped, ParagraphMode::Sentences),
+            grouped
+        );
+
+        // No break before a sentence that continues in lower case.
+        let lower = text.replace("\nSentence 07", "\nand sentence 07");
+        let grouped = format_structured_text_with(&lower, ParagraphMode::Sentences);
+        assert!(grouped.contains("detail. and sentence 07"));
+    }
+
+    #[test]
+    fn keeps_blank_line_paragraphs_in_both_modes() {
+        let text = "First point.\nStill the first.\n\nSecond point.\n\n\nThird point.";
+        let expected = "First point. Still the first.\n\nSecond point.\n\nThird point.";
+        assert_eq!(
+            format_structured_text_with(text, ParagraphMode::BlankLines),
+            expected
+        );
+        assert_eq!(
+            format_structured_text_with(text, ParagraphMode::Sentences),
+            expected
+        );
+    }
+
+    #[test]
+    fn pipeline_runs_only_the_enabled_passes() {
+        let text = "  twenty  five\n\n\n\nitems ";
+        assert_eq!(TextPipeline::default().apply(text), text);
+        let structure = TextPipeline {
+            structure: true,
+            ..TextPipeline::default()
+        };
+        assert_eq!(structure.apply(text), "twenty five\n\nitems");
+        let both = TextPipeline {
+            structure: true,
+            numbers: true,
+            ..TextPipeline::default()
+        };
+        assert_eq!(both.apply(text), "25\n\nitems");
+    }
+
+    /// Free-form text plus text built from a few repeated sentences, so the
+    /// loop-collapsing path is exercised as well as whitespace handling.
+    fn transcript_like() -> impl Strategy<Value = String> {
+        let sentence = prop::sample::select(vec![
+            "Thank you.",
+            "thank you!",
+            "Yes",
+            "Go team!",
+            "1.2.3 is out.",
+            "…",
+            "ありがとう。",
+        ]);
+        let separator =
+            prop::sample::select(vec![" ", "  ", "\n", "\n\n", " \n \n\t", "\r\n", "\u{a0}"]);
+        prop_oneof![
+            "[a-cA-C .!?…\n\t\r\u{a0}\u{3000}]{0,80}",
+            prop::collection::vec((sentence, separator), 0..12).prop_map(|parts| {
+                parts
+                    .into_iter()
+                    .map(|(sentence, separator)| format!("{sentence}{separator}"))
+                    .collect()
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn formatting_is_idempotent(text in transcript_like()) {
+            let once = format_structured_text(&text);
+            prop_assert_eq!(format_structured_text(&once), once);
+        }
+
+        #[test]
+        fn formatting_leaves_single_blank_lines_between_paragraphs(text in transcript_like()) {
+            let formatted = format_structured_text(&text);
+            prop_assert_eq!(formatted.trim(), formatted.as_str());
+            prop_assert!(!formatted.contains("\n\n\n"));
+            if !formatted.is_empty() {
+                for paragraph in formatted.split("\n\n") {
+                    prop_assert!(!paragraph.is_empty());
+                    prop_assert!(!paragraph.contains('\n'));
+                    prop_assert_eq!(paragraph.trim(), paragraph);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn collapses_looped_sentences() {
+        assert_eq!(
+            format_structured_text("I'm done. Thank you. thank you. Thank you! Thank you."),
+            "I'm done. Thank you."
+        );
+        assert_eq!(
+            collapse_repeated_sentences("ありがとう。ありがとう。ありがとう。またね。"),
+            "ありがとう。またね。"
+        );
+    }
+
+    #[test]
+    fn splits_paragraphs_on_blank_lines() {
+        assert_eq!(
+            split_paragraphs("First line\nstill first.\n \n\nSecond.\n\n  "),
+            vec!["First line\nstill first.", "Second."]
+        );
+        assert!(split_paragraphs(" \n\n ").is_empty());
+    }
+
+    #[test]
+    fn flattens_paragraphs_into_one_line() {
+        assert_eq!(
+            flatten_text(" First line\nsame paragraph.\n\n\tSecond.  \n"),
+            "First line same paragraph. Second."
+        );
+    }
+
+    #[test]
+    fn interleaves_sentences_with_their_translations() {
+        let sources = split_sentence_units("Hola. ¿Qué tal?\n\nAdiós");
+        assert_eq!(sources, vec!["Hola.", "¿Qué tal?", "Adiós"]);
+        let translations = vec!["Hello.".to_string(), " ".to_string()];
+        assert_eq!(
+            interleave_bilingual(&sources, &translations),
+            "Hola.\nHello.\n\n¿Qué tal?\n\nAdiós"
+        );
+    }
+
+    #[test]
+    fn prefixes_sentences_and_segments_with_start_times() {
+        let words: Vec<Word> = [
+            ("We", 12.0, 0),
+            ("met.", 12.4, 0),
+            ("It", 13.1, 0),
+            ("rained", 13.3, 1),
+            ("(\"all", 14.0, 1),
+            ("day.\")", 14.2, 1),
+            (" ", 15.0, 1),
+            ("Then", 3_725.5, 2),
+        ]
+        .into_iter()
+        .map(|(text, start, segment)| Word {
+            text: text.to_string(),
+            start: Duration::from_secs_f64(start),
+            end: Duration::from_secs_f64(start + 0.3),
+            segment,
+        })
+        .collect();
+        assert_eq!(
+            with_timestamps(&words, TimestampGranularity::Sentence),
+            "[00:12] We met.\n[00:13] It rained (\"all day.\")\n[1:02:05] Then"
+        );
+        assert_eq!(
+            with_timestamps(&words, TimestampGranularity::Segment),
+            "[00:12] We met. It\n[00:13] rained (\"all day.\")\n[1:02:05] Then"
+        );
+        assert_eq!(with_timestamps(&[], TimestampGranularity::Sentence), "");
+    }
+
+    #[test]
+    fn normalizes_spelled_out_numbers() {
+        assert_eq!(normalize_numbers("three hundred and twelve"), "312");
+        assert_eq!(
+            normalize_numbers("Budget is two thousand five hundred euros."),
+            "Budget is 2500 euros."
+        );
+        assert_eq!(
+            normalize_numbers("In twenty twenty five, twenty-two people"),
+            "In 2025, 22 people"
+        );
+        assert_eq!(normalize_numbers("Nineteen eighty four"), "1984");
+        assert_eq!(
+            normalize_numbers("one million two hundred thousand"),
+            "1200000"
+        );
+        assert_eq!(normalize_numbers("fifteen and more"), "15 and more");
+    }
+
+    #[test]
+    fn leaves_ambiguous_numbers_alone() {
+        for text in [
+            "one of the best",
+            "no one came",
+            "I have two cats",
+            "the twenty first century",
+            "on the twenty-first",
+            "the thirty fifth floor",
+            "twenty sixtieth",
+            "a hundred times",
+            "one and two",
+            "one two three",
+            "thousand hundred",
+        ] {
+            assert_eq!(normalize_numbers(text), text, "{text}");
+        }
+    }
+
+    #[test]
+    fn keeps_short_and_interrupted_repetition() {
+        let chant = "Go team! Go team! We win. Go team!";
+        assert_eq!(format_structured_text(chant), chant);
+        assert_eq!(
+            format_structured_text("Version 1.2.3 is out. It is out."),
+            "Version 1.2.3 is out. It is out."
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Optional system tray icon with a menu to start and stop recording
+//! without raising the window. Built with the `tray` feature on Windows and
+//! macOS; elsewhere, and when the icon cannot be created, [`Tray::new`]
+//! returns `None` and the app runs without one.
+
+/// What a tray menu item asks the app to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    not(all(feature = "tray", any(target_os = "windows", target_os = "macos"))),
+    allow(dead_code)
+)]
+pub enum TrayAction {
+    ToggleRecording,
+    Show,
+    Quit,
+}
+
+#[cfg(all(feature = "tray", any(target_os = "windows", target_os = "macos")))]
+mod imp {
+    use std::sync::mpsc;
+
+    use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+    use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+    use super::TrayAction;
+    use crate::i18n::t;
+
+    const ICON_SIZE: u32 = 32;
+
+    pub struct Tray {
+        _icon: TrayIcon,
+        toggle: MenuItem,
+        events: mpsc::Receiver<MenuEvent>,
+        recording: bool,
+    }
+
+    impl Tray {
+        /// Adds the icon. Menu clicks wake `ctx`, so they are handled even
+        /// while the window is minimized.
+        pub fn new(ctx: &egui::Context) -> Option<Self> {
+            let toggle = MenuItem::with_id("record", t("Start recording"), true, None);
+            let show = MenuItem::with_id("show", t("Show"), true, None);
+            let quit = MenuItem::with_id("quit", t("Quit"), true, None);
+            let menu = Menu::new();
+            let built = menu
+                .append_items(&[&toggle, &show, &PredefinedMenuItem::separator(), &quit])
+                .map_err(|err| err.to_string())
+                .and_then(|()| record_icon().map_err(|err| err.to_string()))
+                .and_then(|icon| {
+                    TrayIconBuilder::new()
+                        .with_menu(Box::new(menu))
+                        .with_tooltip("dict-ai-te")
+                        .with_icon(icon)
+                        .build()
+                        .map_err(|err| err.to_string())
+                });
+            let icon = match built {
+                Ok(icon) => icon,
+                Err(err) => {
+                    log::warn!("Tray icon unavailable: {err}");
+                    return None;
+                }
+            };
+            let (tx, events) = mpsc::channel();
+            let ctx = ctx.clone();
+            MenuEvent::set_event_handler(Some(move |event| {
+                let _ = tx.send(event);
+                ctx.request_repaint();
+            }));
+            Some(Self {
+                _icon: icon,
+                toggle,
+                events,
+                recording: false,
+            })
+        }
+
+        pub fn next_action(&self) -> Option<TrayAction> {
+            while let Ok(event) = self.events.try_recv() {
+                match event.id.as_ref() {
+                    "record" => return Some(TrayAction::ToggleRecording),
+                    "show" => return Some(TrayAction::Show),
+                    "quit" => return Some(TrayAction::Quit),
+                    _ => {}
+                }
+            }
+            None
+        }
+
+        pub fn set_recording(&mut self, recording: bool) {
+            if recording != self.recording {
+                self.recording = recording;
+                self.toggle.set_text(if recording {
+                    t("Stop recording")
+                } else {
+                    t("Start recording")
+                });
+            }
+        }
+    }
+
+    /// A red dot, so no image file has to ship with the binary.
+    fn record_icon() -> Result<Icon, tray_icon::BadIcon> {
+        let center = (ICON_SIZE as f32 - 1.0) / 2.0;
+        let radius = ICON_SIZE as f32 * 0.4;
+        let mut rgba = Vec::with_capacity((ICON_SIZE * ICON_SIZE * 4) as usize);
+        for y in 0..ICON_SIZE {
+            for x in 0..ICON_SIZE {
+                let distance = (x as f32 - center).hypot(y as f32 - center);
+                let alpha = ((radius - distance + 0.5).clamp(0.0, 1.0) * 255.0) as u8;
+                rgba.extend_from_slice(&[220, 40, 40, alpha]);
+            }
+        }
+        Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE)
+    }
+}
+
+#[cfg(not(all(feature = "tray", any(target_os = "windows", target_os = "macos"))))]
+mod imp {
+    use super::TrayAction;
+
+    pub enum Tray {}
+
+    impl Tray {
+        pub fn new(_ctx: &egui::Context) -> Option<Self> {
+            None
+        }
+
+        pub fn next_action(&self) -> Option<TrayAction> {
+            match *self {}
+        }
+
+        pub fn set_recording(&mut self, _recording: bool) {
+            match *self {}
+        }
+    }
+}
+
+pub use imp::Tray;
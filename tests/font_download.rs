@@ -0,0 +1,101 @@
+use dict_ai_te::font_download::download_fonts;
+use dict_ai_te::openai::HttpConfig;
+use httpmock::prelude::*;
+
+const FONT: &[u8] = b"not really a font";
+/// `sha256sum` of [`FONT`].
+const FONT_SHA256: &str = "3c872e99a6658c7b20b92f3d12093a893b832bc210205976d09c7a4acf79f513";
+
+fn dest(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("dictaite-fonts-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[tokio::test]
+async fn downloads_fonts_listed_in_the_checksums() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/noto/SHA256SUMS");
+            then.status(200)
+                .body(format!("{FONT_SHA256}  NotoSansThai-Regular.ttf\n"));
+        })
+        .await;
+    let font = server
+        .mock_async(|when, then| {
+            when.method(GET).path("/noto/NotoSansThai-Regular.ttf");
+            then.status(200).body(FONT);
+        })
+        .await;
+    let dir = dest("ok");
+
+    let written = download_fonts(
+        &HttpConfig::default(),
+        &server.url("/noto/"),
+        &["NotoSansThai-Regular.ttf"],
+        &dir,
+    )
+    .await
+    .unwrap();
+
+    font.assert_async().await;
+    assert_eq!(written, vec![dir.join("NotoSansThai-Regular.ttf")]);
+    assert_eq!(std::fs::read(&written[0]).unwrap(), FONT);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn rejects_unlisted_or_mismatching_fonts() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/SHA256SUMS");
+            then.status(200)
+                .body(format!("{}  NotoSansThai-Regular.ttf\n", "0".repeat(64)));
+        })
+        .await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/NotoSansThai-Regular.ttf");
+            then.status(200).body(FONT);
+        })
+        .await;
+    let dir = dest("mismatch");
+    let http = HttpConfig::default();
+
+    let err = download_fonts(
+        &http,
+        &server.base_url(),
+        &["NotoSansThai-Regular.ttf"],
+        &dir,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Checksum mismatch"), "{err}");
+    assert!(!dir.join("NotoSansThai-Regular.ttf").exists());
+
+    let err = download_fonts(
+        &http,
+        &server.base_url(),
+        &["NotoSansCJK-Regular.ttc"],
+        &dir,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("does not list"), "{err}");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn reports_unreachable_servers() {
+    let err = download_fonts(
+        &HttpConfig::default(),
+        "http://127.0.0.1:9",
+        &["NotoSansThai-Regular.ttf"],
+        &dest("offline"),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("downloading fonts"), "{err}");
+}
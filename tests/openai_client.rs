@@ -0,0 +1,400 @@
+use std::io::Cursor;
+
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
+use dict_ai_te::openai::SpeechEvent;
+use dict_ai_te::{AppError, OpenAiClient};
+use httpmock::prelude::*;
+use serde_json::json;
+
+const API_KEY: &str = "test-key";
+
+fn client_for(server: &MockServer) -> OpenAiClient {
+    OpenAiClient::with_api_key(API_KEY)
+        .unwrap()
+        .with_base_url(server.url("/v1"))
+}
+
+fn wav_bytes(samples: &[i16]) -> Vec<u8> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 24_000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+    for sample in samples {
+        writer.write_sample(*sample).unwrap();
+    }
+    writer.finalize().unwrap();
+    cursor.into_inner()
+}
+
+fn wav_samples(bytes: &[u8]) -> Vec<i16> {
+    hound::WavReader::new(Cursor::new(bytes))
+        .unwrap()
+        .samples::<i16>()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[tokio::test]
+async fn translate_sends_chat_request_and_returns_content() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock_async(|when, then| {
+        when.method(POST)
+            .path("/v1/chat/completions")
+            .header("authorization", "Bearer test-key")
+            .json_body_partial(
+                r#"{"model": "gpt-4o-mini", "messages": [{"role": "system"}, {"role": "user", "content": "Hola"}]}"#,
+            );
+        then.status(200).json_body(json!({
+            "choices": [{"message": {"role": "assistant", "content": "  Hello \n"}}]
+        }));
+    }).await;
+
+    let text = client_for(&server)
+        .translate("  Hola ", "English")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(text, "Hello");
+}
+
+#[tokio::test]
+async fn translate_keeps_code_out_of_the_request_and_restores_it() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/v1/chat/completions")
+                .body_contains("Ejecuta ⟦C1⟧ y luego:\\n\\n⟦C2⟧")
+                .matches(|req| {
+                    !String::from_utf8_lossy(req.body.as_deref().unwrap_or_default())
+                        .contains("cargo")
+                });
+            then.status(200).json_body(json!({
+            "choices": [{"message": {"role": "assistant", "content": "Run ⟦C1⟧ and then:\n\n⟦C2⟧"}}]
+        }));
+        })
+        .await;
+
+    let source = "Ejecuta `cargo fmt` y luego:\n\n```sh\ncargo test --workspace\n```";
+    let text = client_for(&server)
+        .translate(source, "English")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(
+        text,
+        "Run `cargo fmt` and then:\n\n```sh\ncargo test --workspace\n```"
+    );
+}
+
+#[tokio::test]
+async fn records_rate_limit_budget_from_response_headers() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/chat/completions");
+            then.status(200)
+                .header("x-ratelimit-remaining-requests", "499")
+                .header("x-ratelimit-limit-requests", "500")
+                .header("x-ratelimit-remaining-tokens", "199000")
+                .header("x-ratelimit-limit-tokens", "200000")
+                .json_body(json!({
+                    "choices": [{"message": {"role": "assistant", "content": "Hello"}}]
+                }));
+        })
+        .await;
+
+    let client = client_for(&server);
+    assert!(client.rate_limit_budget().is_none());
+    client.translate("Hola", "English").await.unwrap();
+
+    let budget = client.rate_limit_budget().unwrap();
+    assert_eq!(budget.remaining_requests, Some(499));
+    assert_eq!(budget.limit_requests, Some(500));
+    assert_eq!(budget.remaining_tokens, Some(199_000));
+    assert_eq!(budget.limit_tokens, Some(200_000));
+}
+
+#[tokio::test]
+async fn translate_sentences_numbers_lines_and_maps_them_back() {
+    let server = MockServer::start_async().await;
+    let mock = server.mock_async(|when, then| {
+        when.method(POST)
+            .path("/v1/chat/completions")
+            .json_body_partial(
+                r#"{"messages": [{"role": "system"}, {"role": "user", "content": "[1] Hola.\n[2] Adiós."}]}"#,
+            );
+        then.status(200).json_body(json!({
+            "choices": [{"message": {"role": "assistant", "content": "[1] Hello.\n[2] Goodbye."}}]
+        }));
+    }).await;
+
+    let translations = client_for(&server)
+        .translate_sentences(&["Hola.", "Adiós."], "English")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(translations, vec!["Hello.", "Goodbye."]);
+}
+
+#[tokio::test]
+async fn translate_reports_api_errors() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/chat/completions");
+            then.status(401).json_body(json!({
+                "error": {"message": "Incorrect API key provided", "type": "invalid_request_error"}
+            }));
+        })
+        .await;
+
+    let err = client_for(&server)
+        .translate("Hola", "English")
+        .await
+        .unwrap_err();
+
+    let AppError::Translation(message) = err else {
+        panic!("expected a translation error, got {err:?}");
+    };
+    assert!(message.starts_with("401"), "{message}");
+    assert!(message.contains("Incorrect API key provided"), "{message}");
+}
+
+#[tokio::test]
+async fn translate_rejects_empty_choices() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/chat/completions");
+            then.status(200).json_body(json!({"choices": []}));
+        })
+        .await;
+
+    let err = client_for(&server)
+        .translate("Hola", "English")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Translation(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn text_to_speech_returns_binary_audio_as_is() {
+    let server = MockServer::start_async().await;
+    let audio = wav_bytes(&[0, 1_000, -1_000]);
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/v1/audio/speech")
+                .header("authorization", "Bearer test-key")
+                .json_body(json!({
+                    "model": "tts-1",
+                    "input": "Hello there.",
+                    "voice": "nova",
+                    "response_format": "mp3"
+                }));
+            then.status(200)
+                .header("content-type", "audio/wav")
+                .body(audio.clone());
+        })
+        .await;
+
+    let bytes = client_for(&server)
+        .text_to_speech(" Hello there. ", "nova")
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(bytes, audio);
+}
+
+#[tokio::test]
+async fn text_to_speech_decodes_base64_json_payloads() {
+    let server = MockServer::start_async().await;
+    let samples = [0, 2_000, -2_000, 4_000];
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/audio/speech");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({
+                    "data": [{
+                        "b64_json": BASE64_STANDARD.encode(wav_bytes(&samples)),
+                        "format": "wav"
+                    }]
+                }));
+        })
+        .await;
+
+    let bytes = client_for(&server)
+        .text_to_speech("Hello", "nova")
+        .await
+        .unwrap();
+
+    assert_eq!(wav_samples(&bytes), samples);
+}
+
+#[tokio::test]
+async fn text_to_speech_reports_json_without_audio() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/audio/speech");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(json!({"data": []}));
+        })
+        .await;
+
+    let err = client_for(&server)
+        .text_to_speech("Hello", "nova")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Tts(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn text_to_speech_reports_api_errors() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/audio/speech");
+            then.status(400).json_body(json!({
+                "error": {"message": "Invalid voice", "type": "invalid_request_error"}
+            }));
+        })
+        .await;
+
+    let err = client_for(&server)
+        .text_to_speech("Hello", "nova")
+        .await
+        .unwrap_err();
+
+    let AppError::Tts(message) = err else {
+        panic!("expected a TTS error, got {err:?}");
+    };
+    assert!(message.starts_with("400"), "{message}");
+    assert!(message.contains("Invalid voice"), "{message}");
+}
+
+#[tokio::test]
+async fn retries_rate_limited_requests_before_failing() {
+    let server = MockServer::start_async().await;
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v1/chat/completions");
+            then.status(429)
+                .header("retry-after", "0")
+                .json_body(json!({"error": {"message": "Rate limit reached"}}));
+        })
+        .await;
+
+    let err = client_for(&server)
+        .translate("Hola", "English")
+        .await
+        .unwrap_err();
+
+    assert_eq!(mock.hits_async().await, 3);
+    assert!(err.to_string().contains("429"), "{err}");
+}
+
+#[tokio::test]
+async fn streams_raw_pcm_speech_in_chunks() {
+    let server = MockServer::start_async().await;
+    let samples: Vec<i16> = (0..12_000).map(|i| (i % 200) as i16).collect();
+    let body: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+    server
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/v1/audio/speech")
+                .json_body_partial(r#"{"response_format": "pcm"}"#);
+            then.status(200)
+                .header("content-type", "audio/pcm")
+                .body(body);
+        })
+        .await;
+
+    let mut received = Vec::new();
+    let mut chunks = 0;
+    client_for(&server)
+        .stream_text_to_speech("Hello", "nova", |event| {
+            if let SpeechEvent::Audio(chunk) = event {
+                assert_eq!((chunk.sample_rate, chunk.channels), (24_000, 1));
+                received.extend(chunk.samples);
+                chunks += 1;
+            }
+        })
+        .await
+        .unwrap();
+
+    assert!(chunks >= 2, "expected several chunks, got {chunks}");
+    assert_eq!(received, samples);
+}
+
+#[tokio::test]
+async fn downloads_audio_without_sending_the_api_key() {
+    let server = MockServer::start_async().await;
+    let audio = wav_bytes(&[0, 500, -500]);
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(GET).path("/clips/memo.wav").matches(|req| {
+                !req.headers
+                    .as_ref()
+                    .is_some_and(|headers| headers.iter().any(|(name, _)| name == "authorization"))
+            });
+            then.status(200)
+                .header("content-type", "audio/wav")
+                .body(audio.clone());
+        })
+        .await;
+
+    let bytes = client_for(&server)
+        .download_audio(&server.url("/clips/memo.wav"))
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+    assert_eq!(bytes, audio);
+}
+
+#[tokio::test]
+async fn download_rejects_pages_and_reports_http_errors() {
+    let server = MockServer::start_async().await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/page");
+            then.status(200)
+                .header("content-type", "text/html; charset=utf-8")
+                .body("<html></html>");
+        })
+        .await;
+    server
+        .mock_async(|when, then| {
+            when.method(GET).path("/missing.mp3");
+            then.status(404);
+        })
+        .await;
+    let client = client_for(&server);
+
+    let err = client
+        .download_audio(&server.url("/page"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("text/html"), "{err}");
+    let err = client
+        .download_audio(&server.url("/missing.mp3"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Http(_)), "{err:?}");
+    assert!(client
+        .download_audio("ftp://example.com/a.wav")
+        .await
+        .is_err());
+}